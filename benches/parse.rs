@@ -0,0 +1,67 @@
+use bencher::{
+ benchmark_group,
+ benchmark_main,
+ black_box,
+ Bencher
+};
+use lonlat::prelude::*;
+use std::convert::TryFrom;
+
+const SOURCES: [&str; 4] = ["43°3′43.5″N", "-141°21′15.8″", "43°3.725′S", "141.354389"];
+
+/// The legacy `from_dms_str` by `REGEX_DMS`; for the comparison
+fn from_dms_str_regex(source: &str) -> Option<Angle>
+{
+ let capture = REGEX_DMS.captures(source)?;
+ let sign = match (capture.name(REGEX_DMS_PATTERN_SIGN)?.as_str(), capture.name(REGEX_DMS_PATTERN_NWSE)?.as_str())
+ {
+  ("-", _) | ("", "S") | ("", "W") => -ONE,
+  _ => ONE
+ };
+ let value = |name: &str| capture.name(name).map(|m| m.as_str().parse::<f64>().unwrap_or(ZERO)).unwrap_or(ZERO);
+ let degrees = match capture.name(REGEX_DMS_PATTERN_DEG_ONLY)
+ {
+  Some(m) => m.as_str().parse::<f64>().ok()?,
+  None => value(REGEX_DMS_PATTERN_DEG) + value(REGEX_DMS_PATTERN_MIN) / F60 + value(REGEX_DMS_PATTERN_SEC) / F60 / F60
+ };
+ Some(Angle::from_degrees(sign * degrees))
+}
+
+fn angle_parser(b: &mut Bencher)
+{
+ b.iter(|| {
+  for source in SOURCES.iter()
+  {
+   black_box(Angle::from_dms_str(black_box(source)).unwrap());
+  }
+ })
+}
+
+fn angle_regex(b: &mut Bencher)
+{
+ b.iter(|| {
+  for source in SOURCES.iter()
+  {
+   black_box(from_dms_str_regex(black_box(source)).unwrap());
+  }
+ })
+}
+
+fn lonlat_parser(b: &mut Bencher)
+{
+ b.iter(|| black_box(LonLat::try_from(black_box("43°3′43.5″N 141°21′15.8″E")).unwrap()))
+}
+
+fn lonlat_regex(b: &mut Bencher)
+{
+ b.iter(|| {
+  let source = black_box("43°3′43.5″N 141°21′15.8″E");
+  let mut parts = source.splitn(2, ' ');
+  let lat = from_dms_str_regex(parts.next().unwrap()).unwrap();
+  let lon = from_dms_str_regex(parts.next().unwrap()).unwrap();
+  black_box(LonLat::new(lon, lat))
+ })
+}
+
+benchmark_group!(benches, angle_parser, angle_regex, lonlat_parser, lonlat_regex);
+benchmark_main!(benches);
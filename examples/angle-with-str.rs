@@ -15,16 +15,9 @@ fn print_conversions(title: &str, source: &str) -> Result<(), Box<dyn std::error
  println!("  -> angle.as_string_dms_ew  => {}", angle.as_string_dms_ew().unwrap());
  println!("  -> angle.as_string_dms_ns  => {}", angle.as_string_dms_ns().unwrap());
 
- #[cfg(feature = "ja-JP")]
- {
-  println!(r#" #[feature="ja-JP"]"#);
-  println!("  -> angle.as_string_degrees_ja_jp => {}", angle.as_string_degrees_ja_jp());
-  println!("  -> angle.as_string_minutes_ja_jp => {}", angle.as_string_minutes_ja_jp());
-  println!("  -> angle.as_string_seconds_ja_jp => {}", angle.as_string_seconds_ja_jp());
-  println!("  -> angle.as_string_dms_180_ja_jp => {}", angle.as_string_dms_180_ja_jp().unwrap());
-  println!("  -> angle.as_string_dms_ew_ja_jp  => {}", angle.as_string_dms_ew_ja_jp().unwrap());
-  println!("  -> angle.as_string_dms_ns_ja_jp  => {}", angle.as_string_dms_ns_ja_jp().unwrap());
- }
+ let ja_jp = CoordinateFormatter::new().locale(&JaJp);
+ println!("  -> ja_jp.format_lon => {}", ja_jp.format_lon(angle)?);
+ println!("  -> ja_jp.format_lat => {}", ja_jp.format_lat(angle)?);
 
  Ok(())
 }
@@ -0,0 +1,251 @@
+use crate::prelude::*;
+use measurements::{
+ Angle,
+ Length
+};
+
+/// A longitude-latitude aligned bounding box.
+///
+/// - `west` := [-180°..180°), `east` := [-180°..180°]; `west > east` means the box crosses the antimeridian(±180°).
+/// - `south`, `north` := [-90°..90°]
+/// - The full longitude range is represented as `west = -180°`, `east = +180°`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LonLatBounds
+{
+ pub west: Angle,
+ pub south: Angle,
+ pub east: Angle,
+ pub north: Angle
+}
+
+impl LonLatBounds
+{
+ pub fn new(west: Angle, south: Angle, east: Angle, north: Angle) -> Self
+ {
+  let w = west.as_normalize_negative_pi_positive_pi().as_degrees();
+  let e = east.as_normalize_negative_pi_positive_pi().as_degrees();
+  let width = if (east.as_degrees() - west.as_degrees()).abs() >= F360 { F360 } else { (e - w).rem_euclid(F360) };
+  Self::from_west_width(w, width, south.as_degrees(), north.as_degrees())
+ }
+
+ pub fn from_lonlat<T: LonLatGettable>(source: &T) -> Self
+ {
+  let lon = source.get_lon().as_normalize_negative_pi_positive_pi().as_degrees();
+  let lat = source.get_lat().as_degrees();
+  Self::from_west_width(lon, ZERO, lat, lat)
+ }
+
+ /// return the smallest bounds containing all of the sources, or `None` if the sources are empty
+ pub fn from_lonlats<'a, T: 'a + LonLatGettable, I: IntoIterator<Item = &'a T>>(sources: I) -> Option<Self>
+ {
+  let mut lons = Vec::new();
+  let mut south = f64::INFINITY;
+  let mut north = f64::NEG_INFINITY;
+  for source in sources
+  {
+   lons.push(source.get_lon().as_normalize_negative_pi_positive_pi().as_degrees());
+   let lat = source.get_lat().as_degrees();
+   south = south.min(lat);
+   north = north.max(lat);
+  }
+
+  if lons.is_empty()
+  {
+   return None;
+  }
+
+  // The minimal longitude range is the complement of the largest gap between the sorted longitudes.
+  lons.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+  let (mut west, mut gap) = (lons[0], lons[0] + F360 - lons[lons.len() - 1]);
+  for pair in lons.windows(2)
+  {
+   if pair[1] - pair[0] > gap
+   {
+    gap = pair[1] - pair[0];
+    west = pair[1];
+   }
+  }
+
+  Some(Self::from_west_width(west, F360 - gap, south, north))
+ }
+
+ fn from_west_width(west: f64, width: f64, south: f64, north: f64) -> Self
+ {
+  let (west, east) = if width >= F360
+  {
+   (-F180, F180)
+  }
+  else
+  {
+   let west = (west + F180).rem_euclid(F360) - F180;
+   let east = west + width;
+   (west, if east > F180 { east - F360 } else { east })
+  };
+
+  Self {
+   west: Angle::from_degrees(west),
+   south: Angle::from_degrees(south.max(-F90)),
+   east: Angle::from_degrees(east),
+   north: Angle::from_degrees(north.min(F90))
+  }
+ }
+
+ /// return longitude width in degrees := [0..360]
+ fn width_degrees(&self) -> f64
+ {
+  let (w, e) = (self.west.as_degrees(), self.east.as_degrees());
+  if e >= w
+  {
+   e - w
+  }
+  else
+  {
+   e - w + F360
+  }
+ }
+
+ fn contains_lon_degrees(&self, lon: f64) -> bool
+ {
+  (lon - self.west.as_degrees()).rem_euclid(F360) <= self.width_degrees()
+ }
+
+ pub fn width(&self) -> Angle
+ {
+  Angle::from_degrees(self.width_degrees())
+ }
+
+ pub fn height(&self) -> Angle
+ {
+  Angle::from_degrees(self.north.as_degrees() - self.south.as_degrees())
+ }
+
+ pub fn crosses_antimeridian(&self) -> bool
+ {
+  self.west.as_degrees() > self.east.as_degrees()
+ }
+
+ pub fn is_full_longitude(&self) -> bool
+ {
+  self.width_degrees() >= F360
+ }
+
+ pub fn center(&self) -> LonLat
+ {
+  let lon = Angle::from_degrees(self.west.as_degrees() + self.width_degrees() / 2.0);
+  let lat = Angle::from_degrees((self.south.as_degrees() + self.north.as_degrees()) / 2.0);
+  LonLat::new(lon.as_normalize_negative_pi_positive_pi(), lat)
+ }
+
+ pub fn contains<T: LonLatGettable>(&self, target: &T) -> bool
+ {
+  let lat = target.get_lat().as_degrees();
+  lat >= self.south.as_degrees() && lat <= self.north.as_degrees() && self.contains_lon_degrees(target.get_lon().as_degrees())
+ }
+
+ pub fn contains_bounds(&self, other: &Self) -> bool
+ {
+  other.south.as_degrees() >= self.south.as_degrees()
+   && other.north.as_degrees() <= self.north.as_degrees()
+   && (self.is_full_longitude()
+    || (!other.is_full_longitude()
+     && (other.west.as_degrees() - self.west.as_degrees()).rem_euclid(F360) + other.width_degrees() <= self.width_degrees()))
+ }
+
+ pub fn intersects(&self, other: &Self) -> bool
+ {
+  other.south.as_degrees() <= self.north.as_degrees()
+   && other.north.as_degrees() >= self.south.as_degrees()
+   && (self.contains_lon_degrees(other.west.as_degrees()) || other.contains_lon_degrees(self.west.as_degrees()))
+ }
+
+ /// return the intersections; 0, 1 or 2 bounds.
+ /// Note: 2 bounds are returned if the both crosses the antimeridian and overlap at the both ends.
+ pub fn intersection(&self, other: &Self) -> Vec<Self>
+ {
+  let south = self.south.as_degrees().max(other.south.as_degrees());
+  let north = self.north.as_degrees().min(other.north.as_degrees());
+  if south > north
+  {
+   return vec![];
+  }
+
+  if self.is_full_longitude() || other.is_full_longitude()
+  {
+   let lons = if self.is_full_longitude() { other } else { self };
+   return vec![Self::from_west_width(lons.west.as_degrees(), lons.width_degrees(), south, north)];
+  }
+
+  let w0 = self.west.as_degrees();
+  let wa = self.width_degrees();
+  let wb = other.width_degrees();
+  let offset = (other.west.as_degrees() - w0).rem_euclid(F360);
+
+  [offset, offset - F360]
+   .iter()
+   .filter_map(|&o| {
+    let begin = o.max(ZERO);
+    let end = (o + wb).min(wa);
+    match begin <= end
+    {
+     true => Some(Self::from_west_width(w0 + begin, end - begin, south, north)),
+     false => None
+    }
+   })
+   .collect()
+ }
+
+ /// return the smallest bounds containing the both
+ pub fn union(&self, other: &Self) -> Self
+ {
+  let south = self.south.as_degrees().min(other.south.as_degrees());
+  let north = self.north.as_degrees().max(other.north.as_degrees());
+
+  let cover = |a: &Self, b: &Self| {
+   let offset = (b.west.as_degrees() - a.west.as_degrees()).rem_euclid(F360);
+   (a.west.as_degrees(), a.width_degrees().max(offset + b.width_degrees()).min(F360))
+  };
+
+  let (wa, widtha) = cover(self, other);
+  let (wb, widthb) = cover(other, self);
+  let (west, width) = if widtha <= widthb { (wa, widtha) } else { (wb, widthb) };
+
+  Self::from_west_width(west, width, south, north)
+ }
+
+ /// return the smallest bounds containing the both the bounds and the target
+ pub fn extend<T: LonLatGettable>(&self, target: &T) -> Self
+ {
+  self.union(&Self::from_lonlat(target))
+ }
+
+ /// return the bounds expanded to contain all points within `distance` of this bounds.
+ /// Note: The result is conservative; it is computed on the sphere of the smallest radius of curvature of the ellipsoid.
+ pub fn expand_by_distance(&self, distance: Length, ellipsoid: &Ellipsoid) -> Self
+ {
+  let delta = distance.as_meters().max(ZERO) / ellipsoid.minimum_radius_of_curvature();
+  let delta_degrees = delta.to_degrees();
+  let south = self.south.as_degrees() - delta_degrees;
+  let north = self.north.as_degrees() + delta_degrees;
+
+  if south <= -F90 || north >= F90
+  {
+   return Self::from_west_width(-F180, F360, south, north);
+  }
+
+  let max_abs_lat = self.south.as_degrees().abs().max(self.north.as_degrees().abs()).to_radians();
+  let sin_delta = delta.sin();
+  let cos_lat = max_abs_lat.cos();
+  if delta >= PI_DIV_2 || sin_delta >= cos_lat
+  {
+   return Self::from_west_width(-F180, F360, south, north);
+  }
+
+  let delta_lon = (sin_delta / cos_lat).asin().to_degrees();
+  Self::from_west_width(
+   self.west.as_degrees() - delta_lon,
+   self.width_degrees() + delta_lon * 2.0,
+   south,
+   north
+  )
+ }
+}
@@ -0,0 +1,186 @@
+use crate::prelude::*;
+use measurements::{
+ Angle,
+ Length
+};
+
+/// Tolerance [rad] of the turn at a vertex to treat the path as reversed there
+pub const BUFFER_REVERSAL_TOLERANCE: f64 = 1.0e-9;
+
+/// A geodesic circle; the locus of the points at the geodesic distance from the center.
+pub trait GeodesicCircle: LonLatGettable
+{
+ /// return the circle as a counter-clockwise polygon of the `vertices` (at least 3) points.
+ ///
+ /// Note: A circle around a pole or across the antimeridian is a valid `LonLatPolygon` as is.
+ fn geodesic_circle(&self, radius: Length, vertices: usize, ellipsoid: &Ellipsoid) -> LonLatPolygon;
+}
+
+/// A geodesic buffer of a polyline; the region within the distance from the path.
+///
+/// - `vertices` := the number of the vertices of a full circle; the resolution of the round caps and joins.
+/// - The result is a counter-clockwise polygon; round caps at the ends, round joins outside and mitred joins inside the turns.
+/// - A single point is buffered as a `geodesic_circle`.
+///
+/// Note: The inner side of a turn sharper than the segments can absorb(eg, the distance is longer than the segments)
+///       may leave a small self-intersecting loop.
+pub trait GeodesicBuffer
+{
+ fn geodesic_buffer(
+  &self,
+  distance: Length,
+  vertices: usize,
+  ellipsoid: &Ellipsoid
+ ) -> Result<LonLatPolygon, CivilEngineeringLocationError>;
+}
+
+impl<S: LonLatGettable> GeodesicCircle for S
+{
+ fn geodesic_circle(&self, radius: Length, vertices: usize, ellipsoid: &Ellipsoid) -> LonLatPolygon
+ {
+  let vertices = vertices.max(3);
+  // Counter-clockwise; the bearing decreases.
+  LonLatPolygon::new(
+   (0..vertices)
+    .map(|i| {
+     let bearing = Angle::from_radians(-PI_MUL_2 * i as f64 / vertices as f64);
+     self.geodesic_destination(bearing, radius, ellipsoid)
+    })
+    .collect()
+  )
+ }
+}
+
+impl<T: LonLatGettable> GeodesicBuffer for [T]
+{
+ fn geodesic_buffer(&self, distance: Length, vertices: usize, ellipsoid: &Ellipsoid) -> Result<LonLatPolygon, CivilEngineeringLocationError>
+ {
+  let vertices = vertices.max(3);
+  let mut points: Vec<LonLat> = Vec::with_capacity(self.len());
+  for source in self
+  {
+   let lonlat = source.as_lonlat();
+   if points.last() != Some(&lonlat)
+   {
+    points.push(lonlat);
+   }
+  }
+
+  match points.len()
+  {
+   0 => Err(CivilEngineeringLocationError::EmptyPath),
+   1 => Ok(points[0].geodesic_circle(distance, vertices, ellipsoid)),
+   _ => buffer(&points, distance.as_meters(), vertices, ellipsoid)
+  }
+ }
+}
+
+impl GeodesicBuffer for LonLatPath
+{
+ fn geodesic_buffer(&self, distance: Length, vertices: usize, ellipsoid: &Ellipsoid) -> Result<LonLatPolygon, CivilEngineeringLocationError>
+ {
+  self.vertices.geodesic_buffer(distance, vertices, ellipsoid)
+ }
+}
+
+/// An offset of a segment on the right side.
+struct Side
+{
+ points: Vec<LonLat>,
+ initial_azimuth: Angle,
+ final_azimuth: Angle,
+ skip_first: bool
+}
+
+/// Walk the path there and back, and trace the right side; the ring is counter-clockwise around the path.
+fn buffer(points: &[LonLat], distance: f64, vertices: usize, ellipsoid: &Ellipsoid) -> Result<LonLatPolygon, CivilEngineeringLocationError>
+{
+ let step_angle = PI_MUL_2 / vertices as f64;
+ let step = step_angle * ellipsoid.mean_radius();
+
+ let walk: Vec<&LonLat> = points.iter().chain(points.iter().rev().skip(1)).collect();
+ let mut sides = walk
+  .windows(2)
+  .map(|pair| offset(pair[0], pair[1], distance, step, ellipsoid))
+  .collect::<Result<Vec<Side>, CivilEngineeringLocationError>>()?;
+
+ let mut joins = Vec::with_capacity(sides.len());
+ for k in 0..sides.len()
+ {
+  let next = (k + 1) % sides.len();
+  let mut turn = (sides[next].initial_azimuth - sides[k].final_azimuth).as_normalize_negative_pi_positive_pi().as_radians();
+  if PI - turn.abs() < BUFFER_REVERSAL_TOLERANCE
+  {
+   // The ends of the path; turn around to the left.
+   turn = -PI;
+  }
+
+  let mut join = Vec::new();
+  if turn < ZERO
+  {
+   // The outer side; a round join
+   let vertex = walk[k + 1];
+   let from = sides[k].final_azimuth.as_radians() + PI_DIV_2;
+   let count = (turn.abs() / step_angle).ceil() as usize;
+   for j in 1..count
+   {
+    let bearing = Angle::from_radians(from + turn * j as f64 / count as f64);
+    join.push(vertex.geodesic_destination(bearing, Length::from_meters(distance), ellipsoid));
+   }
+  }
+  else if turn > ZERO
+  {
+   // The inner side; a mitred join at the intersection of the offsets
+   let (incoming, outgoing) = (&sides[k].points, &sides[next].points);
+   let a = GeodesicLine::from_points(&incoming[incoming.len() - 2], &incoming[incoming.len() - 1]);
+   let b = GeodesicLine::from_points(&outgoing[0], &outgoing[1]);
+   if let Ok(Some(intersection)) = a.geodesic_segment_intersection(&b, ellipsoid)
+   {
+    if let Some(last) = sides[k].points.last_mut()
+    {
+     *last = intersection;
+    }
+    sides[next].skip_first = true;
+   }
+  }
+  joins.push(join);
+ }
+
+ let mut ring = Vec::new();
+ for (side, join) in sides.iter().zip(joins)
+ {
+  let skip = if side.skip_first { 1 } else { 0 };
+  ring.extend(side.points.iter().skip(skip));
+  ring.extend(join);
+ }
+ Ok(LonLatPolygon::new(ring))
+}
+
+/// return the offset of the segment on the right side; densified by the step [m]
+fn offset(a: &LonLat, b: &LonLat, distance: f64, step: f64, ellipsoid: &Ellipsoid) -> Result<Side, CivilEngineeringLocationError>
+{
+ let inverse = a.geodesic_inverse(b, ellipsoid)?;
+ let length = inverse.distance.as_meters();
+ let count = (length / step).ceil().max(ONE) as usize;
+
+ let points = (0..=count)
+  .map(|i| {
+   let s = length * i as f64 / count as f64;
+   let direct = a.geodesic_direct(inverse.initial_azimuth, Length::from_meters(s), ellipsoid);
+   let point = match i
+   {
+    0 => *a,
+    _ if i == count => *b,
+    _ => direct.destination
+   };
+   point.geodesic_destination(direct.final_azimuth + Angle::from_radians(PI_DIV_2), Length::from_meters(distance), ellipsoid)
+  })
+  .collect();
+
+ Ok(Side {
+  points,
+  initial_azimuth: inverse.initial_azimuth,
+  final_azimuth: inverse.final_azimuth,
+  skip_first: false
+ })
+}
@@ -0,0 +1,55 @@
+use crate::{
+ parse::parse_compact,
+ prelude::*
+};
+
+/// The compact notations without separators; ICAO flight plans, NOTAMs and ARINC 424.
+///
+/// - `(DegreesDecimalMinutes, 0, Suffix)` -> "4303N14121E"
+/// - `(DegreesMinutesSeconds, 0, Suffix)` -> "430343N1412115E"
+/// - `(DegreesMinutesSeconds, 2, Suffix)` -> "430343.50N1412115.80E"
+/// - `(DegreesDecimalMinutes, 3, Prefix)` -> "N4303.725E14121.263"
+/// - `(DegreesMinutesSeconds, 0, SignAlways)` -> "+430343+1412115"; ISO 6709
+pub trait ToStringCompact
+{
+ fn to_string_compact(
+  &self,
+  style: CoordinateStyle,
+  decimals: usize,
+  hemisphere: HemisphereNotation
+ ) -> Result<String, CivilEngineeringLocationError>;
+}
+
+/// The compact notations of `ToStringCompact`; the hemispheres may be before or after the digits.
+pub trait FromCompactStr: Sized
+{
+ fn from_compact_str(source: &str) -> Result<Self, CivilEngineeringLocationError>;
+}
+
+impl<T: LonLatGettable> ToStringCompact for T
+{
+ fn to_string_compact(
+  &self,
+  style: CoordinateStyle,
+  decimals: usize,
+  hemisphere: HemisphereNotation
+ ) -> Result<String, CivilEngineeringLocationError>
+ {
+  CoordinateFormatter::new()
+   .style(style)
+   .decimals(decimals)
+   .zero_padding(true)
+   .symbols(CoordinateSymbols::None)
+   .hemisphere(hemisphere)
+   .separator("")
+   .format(self)
+ }
+}
+
+impl FromCompactStr for LonLat
+{
+ fn from_compact_str(source: &str) -> Result<Self, CivilEngineeringLocationError>
+ {
+  Ok(parse_compact(source, 0)?)
+ }
+}
@@ -1,4 +1,11 @@
-use crate::prelude::*;
+use crate::{
+ parse::{
+  normalized,
+  parse_angle_to_end,
+  parse_angle_units
+ },
+ prelude::*
+};
 
 pub use measurements::{
  Angle,
@@ -6,7 +13,9 @@ pub use measurements::{
 };
 
 use arithmetic_sign::prelude::*;
+#[cfg(feature = "regex")]
 use lazy_static::lazy_static;
+#[cfg(feature = "regex")]
 use regex::Regex;
 use std::convert::TryFrom;
 
@@ -48,8 +57,20 @@ pub const ANGLE_MINIMUM_SECONDS: f64 = 1.0e-4;
 ///     - Decimal digits of this dynamic-range ≈ 13.37 < 15.95 ≈ Decimal digits of IEEE754/Binary64
 ///     - 32-bit < log2(23e+12)≈44.41 < 64-bit
 pub const ANGLE_PI_SECONDS: f64 = F180 * 60.0 * 60.0;
-
+/// The decimal places of the seconds in `as_dms_*`; the same as `ANGLE_MINIMUM_SECONDS`
+pub const ANGLE_DMS_DECIMALS: usize = 4;
+/// The decimal places of the minutes in the DDM strings; 1.0e-3 [min] ≈ 1.85 [m]
+pub const ANGLE_DDM_DECIMALS: usize = 3;
+/// The decimal places of the minutes in `as_ddm_*`; 1.0e-6′ ≈ `ANGLE_MINIMUM_SECONDS`
+pub const ANGLE_DDM_MINUTES_DECIMALS: usize = 6;
+/// The maximum decimal places of the decompositions and `CoordinateFormatter`; 1.0e-12″ is far below the precision of the degrees in f64
+pub const ANGLE_MAXIMUM_DECIMALS: usize = 12;
+/// The decimal places of the seconds in `as_string_hms`; 0.01 [s] = 0.15″
+pub const ANGLE_HMS_DECIMALS: usize = 2;
+
+#[cfg(feature = "regex")]
 lazy_static! {
+ /// Note: The parser does not use it; for the compatibility and the comparisons.
  pub static ref REGEX_DMS: Regex = Regex::new(REGEX_DMS_PATTERN).unwrap();
 }
 
@@ -76,6 +97,7 @@ pub trait AngleAsStringIso800001: AngleAsDms
 }
 
 #[cfg(feature = "ja-JP")]
+#[deprecated(note = "Use `CoordinateFormatter::new().locale(&JaJp)`")]
 pub trait AngleAsStringIso800001JaJp: AngleAsDms
 {
  fn as_string_degrees_ja_jp(&self) -> String;
@@ -92,26 +114,201 @@ pub trait AngleAsStringDmsNwse: AngleAsDms
  fn as_string_dms_ew(&self) -> Result<String, CivilEngineeringLocationError>;
 
  #[cfg(feature = "ja-JP")]
+ #[deprecated(note = "Use `CoordinateFormatter::new().locale(&JaJp).format_lat`")]
  fn as_string_dms_ns_ja_jp(&self) -> Result<String, CivilEngineeringLocationError>;
  #[cfg(feature = "ja-JP")]
+ #[deprecated(note = "Use `CoordinateFormatter::new().locale(&JaJp).format_lon`")]
  fn as_string_dms_ew_ja_jp(&self) -> Result<String, CivilEngineeringLocationError>;
 }
 
+/// Degrees and decimal minutes(DDM) notations; eg, 43°3.725’N
+///
+/// Note: The other locales are `CoordinateFormatter::locale`; eg, "北緯43度3.725分"
+pub trait AngleAsStringDdm: AngleAsDdm
+{
+ fn as_string_ddm_180(&self) -> Result<String, CivilEngineeringLocationError>;
+ fn as_string_ddm_90(&self) -> Result<String, CivilEngineeringLocationError>;
+ fn as_string_ddm_ns(&self) -> Result<String, CivilEngineeringLocationError>;
+ fn as_string_ddm_ew(&self) -> Result<String, CivilEngineeringLocationError>;
+}
+
+/// DDM decompositions; the minutes are rounded at `ANGLE_DDM_MINUTES_DECIMALS` or the decimal places first,
+/// then the carries propagate to the degrees, thus the minutes are always < 60.
+pub trait AngleAsDdm
+{
+ /// return ( degrees := [0..360), minutes )
+ fn as_ddm_360(&self) -> Result<(u16, f64), CivilEngineeringLocationError>
+ {
+  self.as_ddm_360_with_decimals(ANGLE_DDM_MINUTES_DECIMALS)
+ }
+
+ /// return ( sign, degrees := [0..180], minutes )
+ fn as_ddm_180(&self) -> Result<(Sign, u8, f64), CivilEngineeringLocationError>
+ {
+  self.as_ddm_180_with_decimals(ANGLE_DDM_MINUTES_DECIMALS)
+ }
+
+ /// return ( sign, degrees := [0..90], minutes )
+ fn as_ddm_90(&self) -> Result<(Sign, u8, f64), CivilEngineeringLocationError>
+ {
+  self.as_ddm_90_with_decimals(ANGLE_DDM_MINUTES_DECIMALS)
+ }
+
+ /// return ( degrees := [0..360), minutes ); the minutes are rounded at the decimal places
+ fn as_ddm_360_with_decimals(&self, decimals: usize) -> Result<(u16, f64), CivilEngineeringLocationError>;
+
+ /// return ( sign, degrees := [0..180], minutes ); the minutes are rounded at the decimal places
+ fn as_ddm_180_with_decimals(&self, decimals: usize) -> Result<(Sign, u8, f64), CivilEngineeringLocationError>;
+
+ /// return ( sign, degrees := [0..90], minutes ); the minutes are rounded at the decimal places
+ fn as_ddm_90_with_decimals(&self, decimals: usize) -> Result<(Sign, u8, f64), CivilEngineeringLocationError>;
+}
+
+/// DMS decompositions; the seconds are rounded at `ANGLE_DMS_DECIMALS` or the decimal places first,
+/// then the carries propagate to the minutes and the degrees, thus the minutes and the seconds are always < 60.
 pub trait AngleAsDms
 {
- /// return ( degrees := [0..360], minutes, seconds )
- fn as_dms_360(&self) -> Result<(u16, u8, f64), CivilEngineeringLocationError>;
+ /// return ( degrees := [0..360), minutes, seconds )
+ fn as_dms_360(&self) -> Result<(u16, u8, f64), CivilEngineeringLocationError>
+ {
+  self.as_dms_360_with_decimals(ANGLE_DMS_DECIMALS)
+ }
 
- /// return ( sign, degrees := [0..180], minutes, seconds )
- fn as_dms_180(&self) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>;
+ /// return ( sign, degrees := [0..180], minutes, seconds ); +180° is -180°
+ fn as_dms_180(&self) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>
+ {
+  self.as_dms_180_with_decimals(ANGLE_DMS_DECIMALS)
+ }
 
  /// return ( sign, degrees := [0..90], minutes, seconds )
- fn as_dms_90(&self) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>;
+ fn as_dms_90(&self) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>
+ {
+  self.as_dms_90_with_decimals(ANGLE_DMS_DECIMALS)
+ }
+
+ /// return ( degrees := [0..360), minutes, seconds ); the seconds are rounded at the decimal places
+ fn as_dms_360_with_decimals(&self, decimals: usize) -> Result<(u16, u8, f64), CivilEngineeringLocationError>;
+
+ /// return ( sign, degrees := [0..180], minutes, seconds ); the seconds are rounded at the decimal places
+ fn as_dms_180_with_decimals(&self, decimals: usize) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>;
+
+ /// return ( sign, degrees := [0..90], minutes, seconds ); the seconds are rounded at the decimal places
+ fn as_dms_90_with_decimals(&self, decimals: usize) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>;
 
  fn as_minutes(&self) -> f64;
  fn as_seconds(&self) -> f64;
 }
 
+/// The angular units other than the degrees and the radians; cf. `DmsComponent` for the components of the DMS.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum AngularUnit
+{
+ /// gon; 400 per turn, the surveying instruments in Europe
+ Gradians,
+ /// NATO mils; 6400 per turn, the artillery
+ Mils,
+ /// 1 per turn
+ Turns,
+ /// 24 per turn; the right ascension and the hour angle of the astronomy
+ Hours
+}
+
+impl AngularUnit
+{
+ pub fn per_turn(&self) -> f64
+ {
+  match self
+  {
+   AngularUnit::Gradians => 400.0,
+   AngularUnit::Mils => 6400.0,
+   AngularUnit::Turns => ONE,
+   AngularUnit::Hours => 24.0
+  }
+ }
+}
+
+pub trait AngleAsUnits
+{
+ fn as_units(&self, unit: AngularUnit) -> f64;
+
+ fn as_gradians(&self) -> f64
+ {
+  self.as_units(AngularUnit::Gradians)
+ }
+
+ fn as_mils(&self) -> f64
+ {
+  self.as_units(AngularUnit::Mils)
+ }
+
+ fn as_turns(&self) -> f64
+ {
+  self.as_units(AngularUnit::Turns)
+ }
+
+ fn as_hours(&self) -> f64
+ {
+  self.as_units(AngularUnit::Hours)
+ }
+
+ /// return ( hours := [0..24), minutes, seconds ); the seconds are rounded at the decimal places
+ ///
+ /// Note: Up to `ANGLE_MAXIMUM_DECIMALS`; `DecimalsOutOfRange` above it.
+ fn as_hms_with_decimals(&self, decimals: usize) -> Result<(u8, u8, f64), CivilEngineeringLocationError>;
+}
+
+pub trait AngleAsStringUnits: AngleAsUnits
+{
+ /// eg, "100 gon"
+ fn as_string_gradians(&self) -> String;
+ /// eg, "1600 mil"
+ fn as_string_mils(&self) -> String;
+ /// eg, "0.25 tr"
+ fn as_string_turns(&self) -> String;
+ /// eg, "9h25m25.05s"; [0..24h)
+ fn as_string_hms(&self) -> Result<String, CivilEngineeringLocationError>;
+}
+
+/// ## Supported notations of `from_unit_str`
+/// - Gradians: "100 gon", "100grad", "100ᵍ"
+/// - Mils: "1600 mil", "1600 mils"
+/// - Turns: "0.25 tr", "0.25 turn", "0.25 rev"
+/// - Hours: "9h25m25.05s", "9h 25.4m", "9.4h", "9ʰ25ᵐ25.05ˢ"
+///
+/// Note: The units are case-insensitive; the degrees are `FromDmsStr`.
+///       "grad" is always the gradians here, though it is the degrees in `FromDmsStr` with the locale `De`.
+pub trait FromUnits: Sized
+{
+ fn from_units(value: f64, unit: AngularUnit) -> Self;
+
+ fn from_gradians(gradians: f64) -> Self
+ {
+  Self::from_units(gradians, AngularUnit::Gradians)
+ }
+
+ fn from_mils(mils: f64) -> Self
+ {
+  Self::from_units(mils, AngularUnit::Mils)
+ }
+
+ fn from_turns(turns: f64) -> Self
+ {
+  Self::from_units(turns, AngularUnit::Turns)
+ }
+
+ fn from_hours(hours: f64) -> Self
+ {
+  Self::from_units(hours, AngularUnit::Hours)
+ }
+
+ fn from_unit_str(source: &str) -> Result<Self, CivilEngineeringLocationError>
+ {
+  Self::from_unit_str_with_unit(source).map(|(a, _)| a)
+ }
+
+ fn from_unit_str_with_unit(source: &str) -> Result<(Self, AngularUnit), CivilEngineeringLocationError>;
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum AngleDirectionNotation
 {
@@ -120,6 +317,12 @@ pub enum AngleDirectionNotation
  None
 }
 
+/// ## Supported notations
+/// - Decimal degrees: 43.062083, -43.062083, 43.062083N
+/// - DMS: 43°3′43.5″, 43° 3′ 43.5″N
+/// - DDM: 43°3.725′, 43°03.725′N
+///
+/// Note: `from_dms_str` is lenient; `from_dms_str_with` and `ParseOptions::strict()` to validate the values and the extra text.
 pub trait FromDmsStr<T: Sized>: FromDms
 {
  fn from_dms_str(source: &str) -> Result<T, CivilEngineeringLocationError>
@@ -127,7 +330,20 @@ pub trait FromDmsStr<T: Sized>: FromDms
   Self::from_dms_str_with_direction(source).map(|(a, _)| a)
  }
 
- fn from_dms_str_with_direction(source: &str) -> Result<(T, AngleDirectionNotation), CivilEngineeringLocationError>;
+ fn from_dms_str_with_direction(source: &str) -> Result<(T, AngleDirectionNotation), CivilEngineeringLocationError>
+ {
+  Self::from_dms_str_with_direction_with(source, &ParseOptions::default())
+ }
+
+ fn from_dms_str_with(source: &str, options: &ParseOptions) -> Result<T, CivilEngineeringLocationError>
+ {
+  Self::from_dms_str_with_direction_with(source, options).map(|(a, _)| a)
+ }
+
+ fn from_dms_str_with_direction_with(
+  source: &str,
+  options: &ParseOptions
+ ) -> Result<(T, AngleDirectionNotation), CivilEngineeringLocationError>;
 }
 
 impl AngleAsNormalize for Angle
@@ -142,32 +358,8 @@ impl AngleAsNormalize for Angle
  fn as_normalize_negative_pi_positive_pi(&self) -> Self
  {
   let rad = self.as_radians();
-
-  let rad = if rad >= PI
-  {
-   // -> [π..2π)
-   let rad = rad % PI_MUL_2;
-   // -> [-π..0)
-   rad - PI_MUL_2
-  }
-  else if rad < PI
-  {
-   let negative_rotation_count = (rad / PI_MUL_2).floor();
-   // -> [0..2π)
-   let rad = rad + PI_MUL_2 * (negative_rotation_count + 1.0);
-   if rad >= PI
-   {
-    rad - PI_MUL_2
-   }
-   else
-   {
-    rad
-   }
-  }
-  else
-  {
-   rad
-  };
+  // -> [-π..π)
+  let rad = rad - PI_MUL_2 * ((rad + PI) / PI_MUL_2).floor();
   Self::from_radians(rad)
  }
 
@@ -216,24 +408,23 @@ impl AngleAsStringIso800001 for Angle
 
  fn as_string_dms_360(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (deg, min, sec) = self.as_dms_360()?;
+  let (deg, min, sec) = self.as_dms_360_with_decimals(1)?;
   Ok(format!("{}{}°{}’{:.1}”", Sign::Positive, deg, min, sec))
  }
 
  fn as_string_dms_180(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (sign, deg, min, sec) = self.as_dms_180()?;
-  Ok(format!("{}{}°{}’{:.1}”", sign, deg, min, sec))
+  CoordinateFormatter::new().format_lon(*self)
  }
 
  fn as_string_dms_90(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (sign, deg, min, sec) = self.as_dms_90()?;
-  Ok(format!("{}{}°{}’{:.1}”", sign, deg, min, sec))
+  CoordinateFormatter::new().format_lat(*self)
  }
 }
 
 #[cfg(feature = "ja-JP")]
+#[allow(deprecated)]
 impl AngleAsStringIso800001JaJp for Angle
 {
  fn as_string_degrees_ja_jp(&self) -> String
@@ -253,131 +444,208 @@ impl AngleAsStringIso800001JaJp for Angle
 
  fn as_string_dms_360_ja_jp(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (deg, min, sec) = self.as_dms_360()?;
+  let (deg, min, sec) = self.as_dms_360_with_decimals(1)?;
   Ok(format!("{}{}度{}分{:.1}秒", Sign::Positive, deg, min, sec))
  }
 
  fn as_string_dms_180_ja_jp(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (sign, deg, min, sec) = self.as_dms_180()?;
-  Ok(format!("{}{}度{}分{:.1}秒", sign, deg, min, sec))
+  CoordinateFormatter::new().locale(&JaJp).hemisphere(HemisphereNotation::SignAlways).format_lon(*self)
  }
 
  fn as_string_dms_90_ja_jp(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (sign, deg, min, sec) = self.as_dms_90()?;
-  Ok(format!("{}{}度{}分{:.1}秒", sign, deg, min, sec))
+  CoordinateFormatter::new().locale(&JaJp).hemisphere(HemisphereNotation::SignAlways).format_lat(*self)
  }
 }
 
 impl AngleAsDms for Angle
 {
- fn as_dms_360(&self) -> Result<(u16, u8, f64), CivilEngineeringLocationError>
+ fn as_dms_360_with_decimals(&self, decimals: usize) -> Result<(u16, u8, f64), CivilEngineeringLocationError>
  {
-  if self.as_degrees().is_infinite()
-  {
-   Err(CivilEngineeringLocationError::Infinite)?;
-  }
+  let units = units_per_degree(60 * 60, decimals)?;
+  let (_, total) = round_to_units(*self, AngleRange::Positive360, units)?;
+  let (deg, min, sec) = split_dms(total, units / (60 * 60));
+  Ok((deg as u16, min, sec))
+ }
 
-  let degrees = self.as_normalize_0_pi().as_degrees();
+ fn as_dms_180_with_decimals(&self, decimals: usize) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>
+ {
+  let units = units_per_degree(60 * 60, decimals)?;
+  let (sign, total) = round_to_units(*self, AngleRange::Longitude180, units)?;
+  let (deg, min, sec) = split_dms(total, units / (60 * 60));
+  Ok((sign, deg as u8, min, sec))
+ }
 
-  let part_of_degrees = degrees.floor() as u16;
-  let part_of_minutes = ((degrees % ONE) * F60).floor() as u8;
-  let part_of_seconds = (((degrees % ONE) * F60) % ONE) * F60;
+ fn as_dms_90_with_decimals(&self, decimals: usize) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>
+ {
+  let units = units_per_degree(60 * 60, decimals)?;
+  let (sign, total) = round_to_units(*self, AngleRange::Latitude90, units)?;
+  let (deg, min, sec) = split_dms(total, units / (60 * 60));
+  Ok((sign, deg as u8, min, sec))
+ }
 
-  Ok((part_of_degrees, part_of_minutes, part_of_seconds))
+ fn as_minutes(&self) -> f64
+ {
+  self.as_degrees() * F60
  }
 
- fn as_dms_180(&self) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>
+ fn as_seconds(&self) -> f64
  {
-  if self.as_degrees().is_infinite()
-  {
-   Err(CivilEngineeringLocationError::Infinite)?;
-  }
+  self.as_minutes() * F60
+ }
+}
 
-  // tsf; Total-Seconds in Float
-  let tsf = self.as_normalize_negative_pi_positive_pi().as_seconds();
-  let part_of_sign = Sign::try_from(tsf)?;
-  // tsm: Total-Seconds Magnified
-  let tsm = (tsf / ANGLE_MINIMUM_SECONDS).round() as i64;
-  // tsi; Total-Seconds in Integer
-  let tsi = tsm / ((ONE / ANGLE_MINIMUM_SECONDS) as i64);
-  let part_of_degrees = tsi / 60 / 60;
-  let _p = part_of_degrees;
-  // ds; part-of-Degrees in Seconds
-  let ds = part_of_degrees * 60 * 60;
-  let part_of_minutes = (tsi - ds) / 60;
-  // ms; part-of-Minutes in Seconds
-  let ms = part_of_minutes * 60;
-  // dm; part-of-Degrees + part-of-Minutes in Seconds
-  let dms = ds + ms;
-  // dmf; part-of-Degrees + part-of-Minutes in Seconds Magnified
-  let dmsm = dms * ((ONE / ANGLE_MINIMUM_SECONDS) as i64);
-  let part_of_seconds = (tsm - dmsm).abs() as f64 * ANGLE_MINIMUM_SECONDS;
-  let part_of_minutes = part_of_minutes.abs() as u8;
-  let part_of_degrees = part_of_degrees.abs() as u8;
-
-  Ok((part_of_sign, part_of_degrees, part_of_minutes, part_of_seconds))
+impl AngleAsDdm for Angle
+{
+ fn as_ddm_360_with_decimals(&self, decimals: usize) -> Result<(u16, f64), CivilEngineeringLocationError>
+ {
+  let units = units_per_degree(60, decimals)?;
+  let (_, total) = round_to_units(*self, AngleRange::Positive360, units)?;
+  let (deg, min) = split_ddm(total, units / 60);
+  Ok((deg as u16, min))
  }
 
- fn as_dms_90(&self) -> Result<(Sign, u8, u8, f64), CivilEngineeringLocationError>
+ fn as_ddm_180_with_decimals(&self, decimals: usize) -> Result<(Sign, u8, f64), CivilEngineeringLocationError>
  {
-  if self.as_degrees().is_infinite()
-  {
-   Err(CivilEngineeringLocationError::Infinite)?;
-  }
+  let units = units_per_degree(60, decimals)?;
+  let (sign, total) = round_to_units(*self, AngleRange::Longitude180, units)?;
+  let (deg, min) = split_ddm(total, units / 60);
+  Ok((sign, deg as u8, min))
+ }
 
-  let degrees = self.as_normalize_negative_half_pi_positive_half_pi().as_degrees();
+ fn as_ddm_90_with_decimals(&self, decimals: usize) -> Result<(Sign, u8, f64), CivilEngineeringLocationError>
+ {
+  let units = units_per_degree(60, decimals)?;
+  let (sign, total) = round_to_units(*self, AngleRange::Latitude90, units)?;
+  let (deg, min) = split_ddm(total, units / 60);
+  Ok((sign, deg as u8, min))
+ }
+}
 
-  let part_of_sign = Sign::try_from(degrees)?;
-  let part_of_degrees = degrees.abs().floor() as u8;
-  let part_of_minutes = ((degrees.abs() % ONE) * F60).floor() as u8;
-  let part_of_seconds = (((degrees.abs() % ONE) * F60) % ONE) * F60;
+impl AngleAsStringDdm for Angle
+{
+ fn as_string_ddm_180(&self) -> Result<String, CivilEngineeringLocationError>
+ {
+  ddm_formatter().format_lon(*self)
+ }
 
-  Ok((part_of_sign, part_of_degrees, part_of_minutes, part_of_seconds))
+ fn as_string_ddm_90(&self) -> Result<String, CivilEngineeringLocationError>
+ {
+  ddm_formatter().format_lat(*self)
  }
 
- fn as_minutes(&self) -> f64
+ fn as_string_ddm_ns(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  self.as_degrees() * F60
+  ddm_formatter().hemisphere(HemisphereNotation::Suffix).format_lat(*self)
  }
 
- fn as_seconds(&self) -> f64
+ fn as_string_ddm_ew(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  self.as_minutes() * F60
+  ddm_formatter().hemisphere(HemisphereNotation::Suffix).format_lon(*self)
+ }
+}
+
+/// The range of the decomposition of an angle.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub(crate) enum AngleRange
+{
+ /// [0..360)
+ Positive360,
+ /// [-180..180)
+ Longitude180,
+ /// [-90..90]
+ Latitude90
+}
+
+/// return ( sign, |angle| in the integer units ); rounded to the nearest unit after the normalization.
+///
+/// Note: The rounding may reach the end of the range; 360° wraps to 0°, and +180° wraps to -180°.
+///       The units must be from `units_per_degree`; 360° × the units fit in u64 up to `ANGLE_MAXIMUM_DECIMALS`.
+pub(crate) fn round_to_units(angle: Angle, range: AngleRange, units_per_degree: u64) -> Result<(Sign, u64), CivilEngineeringLocationError>
+{
+ let degrees = angle.as_degrees();
+ if degrees.is_nan()
+ {
+  Err(CivilEngineeringLocationError::Nan)?;
+ }
+ if degrees.is_infinite()
+ {
+  Err(CivilEngineeringLocationError::Infinite)?;
+ }
+
+ let degrees = match range
+ {
+  AngleRange::Positive360 => angle.as_normalize_0_pi(),
+  AngleRange::Longitude180 => angle.as_normalize_negative_pi_positive_pi(),
+  AngleRange::Latitude90 => angle.as_normalize_negative_half_pi_positive_half_pi()
+ }
+ .as_degrees();
+
+ let total = (degrees.abs() * units_per_degree as f64).round() as u64;
+ let sign = if degrees < ZERO && total != 0 { Sign::Negative } else { Sign::Positive };
+ Ok(match range
+ {
+  AngleRange::Positive360 if total >= 360 * units_per_degree => (Sign::Positive, 0),
+  AngleRange::Longitude180 if total >= 180 * units_per_degree => (Sign::Negative, 180 * units_per_degree),
+  _ => (sign, total)
+ })
+}
+
+/// return the units of the last decimal place per degree; units := the units of the integer part per degree, eg, 60 × 60 for the seconds
+pub(crate) fn units_per_degree(units: u64, decimals: usize) -> Result<u64, CivilEngineeringLocationError>
+{
+ if decimals > ANGLE_MAXIMUM_DECIMALS
+ {
+  Err(CivilEngineeringLocationError::DecimalsOutOfRange(decimals))?;
  }
+ Ok(units * 10u64.pow(decimals as u32))
+}
+
+/// return ( degrees, minutes, seconds ) of the total seconds × scale
+fn split_dms(total: u64, scale: u64) -> (u64, u8, f64)
+{
+ let (per_degree, per_minute) = (60 * 60 * scale, 60 * scale);
+ let seconds = (total % per_minute) as f64 / scale as f64;
+ (total / per_degree, (total % per_degree / per_minute) as u8, seconds)
+}
+
+/// return ( degrees, minutes ) of the total minutes × scale
+fn split_ddm(total: u64, scale: u64) -> (u64, f64)
+{
+ let per_degree = 60 * scale;
+ (total / per_degree, (total % per_degree) as f64 / scale as f64)
+}
+
+pub(crate) fn ddm_formatter() -> CoordinateFormatter
+{
+ CoordinateFormatter::new()
+  .style(CoordinateStyle::DegreesDecimalMinutes)
+  .decimals(ANGLE_DDM_DECIMALS)
 }
 
 impl AngleAsStringDmsNwse for Angle
 {
  fn as_string_dms_ns(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (sign, deg, min, sec) = self.as_dms_90()?;
-  let ns = sign.to_string_ns();
-  Ok(format!("{}°{}’{:.1}”{}", deg, min, sec, ns))
+  CoordinateFormatter::new().hemisphere(HemisphereNotation::Suffix).format_lat(*self)
  }
 
  fn as_string_dms_ew(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (sign, deg, min, sec) = self.as_dms_180()?;
-  let ew = sign.to_string_ew();
-  Ok(format!("{}°{}’{:.1}”{}", deg, min, sec, ew))
+  CoordinateFormatter::new().hemisphere(HemisphereNotation::Suffix).format_lon(*self)
  }
 
  #[cfg(feature = "ja-JP")]
  fn as_string_dms_ns_ja_jp(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (sign, deg, min, sec) = self.as_dms_90()?;
-  let ns_ja_jp = sign.to_string_ns_ja_jp();
-  Ok(format!("{}{}度{}分{:.1}秒", ns_ja_jp, deg, min, sec))
+  CoordinateFormatter::new().locale(&JaJp).format_lat(*self)
  }
 
  #[cfg(feature = "ja-JP")]
  fn as_string_dms_ew_ja_jp(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let (sign, deg, min, sec) = self.as_dms_180()?;
-  let ew_ja_jp = sign.to_string_ew_ja_jp();
-  Ok(format!("{}{}度{}分{:.1}秒", ew_ja_jp, deg, min, sec))
+  CoordinateFormatter::new().locale(&JaJp).format_lon(*self)
  }
 }
 
@@ -416,98 +684,69 @@ impl FromDms for Angle
  }
 }
 
-impl FromDmsStr<Angle> for Angle
+impl AngleAsUnits for Angle
 {
- fn from_dms_str_with_direction(source: &str) -> Result<(Angle, AngleDirectionNotation), CivilEngineeringLocationError>
+ fn as_units(&self, unit: AngularUnit) -> f64
  {
-  let capture = REGEX_DMS
-   .captures(source)
-   .ok_or(CivilEngineeringLocationError::CouldNotParseFromIso800001Dms(source.into()))?;
+  self.as_degrees() / F360 * unit.per_turn()
+ }
 
-  // eprintln!("{:?}", &capture);
+ fn as_hms_with_decimals(&self, decimals: usize) -> Result<(u8, u8, f64), CivilEngineeringLocationError>
+ {
+  // 1° = 4 minutes = 240 seconds of the time
+  let units = units_per_degree(240, decimals)?;
+  let (_, total) = round_to_units(*self, AngleRange::Positive360, units)?;
+  let (hours, minutes, seconds) = split_dms(total, units / 240);
+  Ok((hours as u8, minutes, seconds))
+ }
+}
 
-  let (sign, direction) = match capture
-   .name(REGEX_DMS_PATTERN_SIGN)
-   .ok_or(CivilEngineeringLocationError::ParseErrorAroundSign)?
-   .as_str()
-  {
-   "-" => (-ONE, AngleDirectionNotation::None),
-   "+" => (ONE, AngleDirectionNotation::None),
-   "" =>
-   {
-    match capture
-     .name(REGEX_DMS_PATTERN_NWSE)
-     .ok_or(CivilEngineeringLocationError::ParseErrorAroundNwse)?
-     .as_str()
-    {
-     "N" => (ONE, AngleDirectionNotation::Latitude),
-     "W" => (-ONE, AngleDirectionNotation::Longitude),
-     "S" => (-ONE, AngleDirectionNotation::Latitude),
-     "E" => (ONE, AngleDirectionNotation::Longitude),
-
-     #[cfg(not(feature = "ja-JP"))]
-     "" => (ONE, AngleDirectionNotation::None),
-
-     #[cfg(feature = "ja-JP")]
-     "" =>
-     {
-      match capture
-       .name(REGEX_DMS_PATTERN_NWSE_JA_JP)
-       .ok_or(CivilEngineeringLocationError::ParseErrorAroundNwseJaJp)?
-       .as_str()
-      {
-       "北緯" => (ONE, AngleDirectionNotation::Latitude),
-       "西経" => (-ONE, AngleDirectionNotation::Longitude),
-       "南緯" => (-ONE, AngleDirectionNotation::Latitude),
-       "東経" => (ONE, AngleDirectionNotation::Longitude),
-       "" => (ONE, AngleDirectionNotation::None),
-
-       _ => Err(CivilEngineeringLocationError::ParseErrorAroundNwse)?
-      }
-     },
-
-     _ => Err(CivilEngineeringLocationError::ParseErrorAroundNwse)?
-    }
-   },
-   _ => Err(CivilEngineeringLocationError::ParseErrorAroundNwse)?
-  };
+impl AngleAsStringUnits for Angle
+{
+ fn as_string_gradians(&self) -> String
+ {
+  format!("{} gon", self.as_gradians())
+ }
 
-  if capture.name(REGEX_DMS_PATTERN_DEG).is_none()
-   && capture.name(REGEX_DMS_PATTERN_MIN).is_none()
-   && capture.name(REGEX_DMS_PATTERN_SEC).is_none()
-  {
-   match capture.name(REGEX_DMS_PATTERN_DEG_ONLY)
-   {
-    Some(m) =>
-    {
-     let deg_only = m.as_str().parse::<f64>()?;
-     Ok((Self::from_degrees(deg_only), direction))
-    },
-    None => Err(CivilEngineeringLocationError::CouldNotDetectAnglePattern)
-   }
-  }
-  else
-  {
-   let deg = match capture.name(REGEX_DMS_PATTERN_DEG)
-   {
-    Some(m) => m.as_str().parse::<f64>()?,
-    None => 0.0
-   };
-
-   let min = match capture.name(REGEX_DMS_PATTERN_MIN)
-   {
-    Some(m) => m.as_str().parse::<f64>()?,
-    None => 0.0
-   };
-
-   let sec = match capture.name(REGEX_DMS_PATTERN_SEC)
-   {
-    Some(m) => m.as_str().parse::<f64>()?,
-    None => 0.0
-   };
-
-   Ok((Self::from_dms(sign * deg, min, sec)?, direction))
-  }
+ fn as_string_mils(&self) -> String
+ {
+  format!("{} mil", self.as_mils())
+ }
+
+ fn as_string_turns(&self) -> String
+ {
+  format!("{} tr", self.as_turns())
+ }
+
+ fn as_string_hms(&self) -> Result<String, CivilEngineeringLocationError>
+ {
+  let (hours, minutes, seconds) = self.as_hms_with_decimals(ANGLE_HMS_DECIMALS)?;
+  Ok(format!("{}h{}m{:.*}s", hours, minutes, ANGLE_HMS_DECIMALS, seconds))
+ }
+}
+
+impl FromUnits for Angle
+{
+ fn from_units(value: f64, unit: AngularUnit) -> Self
+ {
+  Self::from_degrees(value / unit.per_turn() * F360)
+ }
+
+ fn from_unit_str_with_unit(source: &str) -> Result<(Self, AngularUnit), CivilEngineeringLocationError>
+ {
+  Ok(parse_angle_units(source, 0)?)
+ }
+}
+
+impl FromDmsStr<Angle> for Angle
+{
+ fn from_dms_str_with_direction_with(
+  source: &str,
+  options: &ParseOptions
+ ) -> Result<(Angle, AngleDirectionNotation), CivilEngineeringLocationError>
+ {
+  let parsed = normalized(source, 0, options, |source, offset| parse_angle_to_end(source, offset, options))?;
+  Ok((parsed.angle, parsed.direction))
  }
 }
 
@@ -516,6 +755,7 @@ pub trait ToStringNS
  fn to_string_ns(&self) -> &str;
 
  #[cfg(feature = "ja-JP")]
+ #[deprecated(note = "Use `JaJp.hemispheres()`; ( north, south, .. )")]
  fn to_string_ns_ja_jp(&self) -> &str;
 }
 
@@ -538,6 +778,7 @@ pub trait ToStringEW
  fn to_string_ew(&self) -> &str;
 
  #[cfg(feature = "ja-JP")]
+ #[deprecated(note = "Use `JaJp.hemispheres()`; ( .., east, west )")]
  fn to_string_ew_ja_jp(&self) -> &str;
 }
 
@@ -0,0 +1,68 @@
+use measurements::Length;
+
+/// An ellipsoid of revolution; `a` := semi-major axis [m], `f` := flattening
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ellipsoid
+{
+ pub a: f64,
+ pub f: f64
+}
+
+impl Ellipsoid
+{
+ pub const WGS84: Ellipsoid = Ellipsoid {
+  a: 6_378_137.0,
+  f: 1.0 / 298.257_223_563
+ };
+
+ pub const GRS80: Ellipsoid = Ellipsoid {
+  a: 6_378_137.0,
+  f: 1.0 / 298.257_222_101
+ };
+
+ pub fn new(semi_major_axis: Length, flattening: f64) -> Self
+ {
+  Self {
+   a: semi_major_axis.as_meters(),
+   f: flattening
+  }
+ }
+
+ /// A sphere is an ellipsoid with zero flattening.
+ pub fn sphere(radius: Length) -> Self
+ {
+  Self::new(radius, 0.0)
+ }
+
+ /// return semi-minor axis [m]
+ pub fn b(&self) -> f64
+ {
+  self.a * (1.0 - self.f)
+ }
+
+ /// return first eccentricity squared
+ pub fn e2(&self) -> f64
+ {
+  self.f * (2.0 - self.f)
+ }
+
+ /// return mean radius [m]; (2a+b)/3
+ pub fn mean_radius(&self) -> f64
+ {
+  (2.0 * self.a + self.b()) / 3.0
+ }
+
+ /// return the smallest radius of curvature [m]; the meridional radius at the equator, a(1-e²)
+ pub fn minimum_radius_of_curvature(&self) -> f64
+ {
+  self.a * (1.0 - self.e2())
+ }
+}
+
+impl Default for Ellipsoid
+{
+ fn default() -> Self
+ {
+  Self::WGS84
+ }
+}
@@ -1,7 +1,65 @@
 use arithmetic_sign::error::ArithmeticSignError;
 use measurements::Angle;
+use std::ops::Range;
 use thiserror::Error;
 
+/// The part of a coordinate notation where a parse error occurred.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ParseComponent
+{
+ Sign,
+ Degrees,
+ Minutes,
+ Seconds,
+ /// N, S, E, W or the words such as 北緯
+ Hemisphere,
+ /// Between the latitude and the longitude, or before the altitude
+ Separator,
+ Altitude,
+ /// After the notation
+ End,
+ /// The latitude and the longitude could not be told apart; `ParseAxisOrder::Auto`
+ AxisOrder,
+ /// The value of `FromUnits::from_unit_str`; eg, "100" of "100 gon"
+ Value,
+ /// The unit of `FromUnits::from_unit_str`; eg, "gon"
+ Unit
+}
+
+/// A parse error of an angle or a coordinate with the byte span in the source; eg, to highlight the input.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Could not parse the {component} at {span:?}; found {found:?}, expected {expected}.")]
+pub struct ParseError
+{
+ /// The byte span in the source; empty at the end of the source if something is missing
+ pub span: Range<usize>,
+ pub component: ParseComponent,
+ /// A hint for the user; eg, "′ after the minutes"
+ pub expected: &'static str,
+ pub found: String
+}
+
+impl std::fmt::Display for ParseComponent
+{
+ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+ {
+  f.write_str(match self
+  {
+   ParseComponent::Sign => "sign",
+   ParseComponent::Degrees => "degrees",
+   ParseComponent::Minutes => "minutes",
+   ParseComponent::Seconds => "seconds",
+   ParseComponent::Hemisphere => "hemisphere",
+   ParseComponent::Separator => "separator",
+   ParseComponent::Altitude => "altitude",
+   ParseComponent::End => "end",
+   ParseComponent::AxisOrder => "axis order",
+   ParseComponent::Value => "value",
+   ParseComponent::Unit => "unit"
+  })
+ }
+}
+
 #[derive(Debug, Error)]
 pub enum CivilEngineeringLocationError
 {
@@ -20,25 +78,54 @@ pub enum CivilEngineeringLocationError
  #[error("Could not parse the string to GeoURI.; source = {0:?}")]
  GeoUriParseError(String),
 
- #[error("Could not parse in Angle::from_iso_80000_1_dms.; source = {0:?}")]
- CouldNotParseFromIso800001Dms(String),
+ #[error("Could not parse the string to a link of a map service.; source = {0:?}")]
+ MapUrlParseError(String),
 
  #[error("")]
  ParseFloatError(#[from] std::num::ParseFloatError),
 
- #[error("Parse error occured around a sign(+/-).")]
- ParseErrorAroundSign,
+ #[error(transparent)]
+ ParseError(#[from] ParseError),
+
+ #[error("The decimal places are more than ANGLE_MAXIMUM_DECIMALS.; decimals = {0}")]
+ DecimalsOutOfRange(usize),
+
+ #[error("The geodesic inverse problem did not converge; the points may be nearly antipodal.")]
+ GeodesicDidNotConverge,
+
+ #[error("The great-circle is undefined; the points are antipodal.")]
+ GreatCircleAntipodal,
+
+ #[error("The path has no vertices.")]
+ EmptyPath,
+
+ #[error("Invalid character in the encoded polyline.; index = {0}, character = {1:?}")]
+ EncodedPolylineInvalidCharacter(usize, char),
+
+ #[error("The encoded polyline ended in the middle of a value.; index = {0}")]
+ EncodedPolylineUnexpectedEnd(usize),
+
+ #[error("The encoded polyline ended without the longitude of the last point.; index = {0}")]
+ EncodedPolylineMissingLongitude(usize),
+
+ #[error("A value in the encoded polyline is too long.; index = {0}")]
+ EncodedPolylineOverflow(usize),
+
+ #[error("The digits of the encoded polyline precision are too many.; digits = {0}")]
+ EncodedPolylinePrecisionOutOfRange(u8),
+
+ #[error("A value is out of the range of the encoded polyline.; vertex = {0}")]
+ EncodedPolylineValueOutOfRange(usize),
 
- #[error("Could not detect an angle pattern.")]
- CouldNotDetectAnglePattern,
+ #[error("The rhumb line reaches a pole before the distance.")]
+ RhumbLineBeyondPole,
 
- #[error("Parse error occured around a NWSE.")]
- ParseErrorAroundNwse,
+ #[error("The line is undefined; the points are identical or antipodal.")]
+ IntersectionUndefinedLine,
 
- #[cfg(feature = "ja-JP")]
- #[error("Parse error occured around a NWSE/ja-JP such as 北緯, 西経, 南緯 or 東経.")]
- ParseErrorAroundNwseJaJp,
+ #[error("The lines are coincident; they have no isolated intersection.")]
+ IntersectionCoincident,
 
- #[error("LonLat::from was failed. Unknown source pattern.")]
- LonLatFromStrUnknownPattern
+ #[error("The lines are (nearly) parallel at the intersection; it could not be determined.")]
+ IntersectionParallel
 }
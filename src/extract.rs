@@ -0,0 +1,35 @@
+use crate::{
+ parse::scan_lonlats,
+ prelude::*
+};
+use std::ops::Range;
+
+/// A coordinate found in a text by `extract_lonlats`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedLonLat
+{
+ pub lonlat: LonLat,
+ /// The byte span in the text; eg, `&text[extracted.span.clone()]` -> "43.06, 141.35"
+ pub span: Range<usize>
+}
+
+/// Find all the coordinates in a free text; eg, e-mails, tickets and chats.
+///
+/// - The notations of `LonLat::parse_with`; "43°3′43.5″N 141°21′15.8″E", "N 43.06 E 141.35", "北緯43度3分43.5秒 東経141度21分15.8秒"
+/// - The geo URIs; "geo:43.06,141.35"
+/// - The decimal pairs; "43.06, 141.35", but not the pairs of the integers such as "10 15"
+///
+/// Note: The values are checked as `ParseMode::Strict`; minutes and seconds < 60, latitude ±90°, longitude ±180°.
+pub fn extract_lonlats(text: &str) -> Vec<ExtractedLonLat>
+{
+ extract_lonlats_with(text, &ParseOptions::default())
+}
+
+/// `extract_lonlats` with the locales and the `ParseAxisOrder` of the options; the `ParseMode` is ignored.
+pub fn extract_lonlats_with(text: &str, options: &ParseOptions) -> Vec<ExtractedLonLat>
+{
+ scan_lonlats(text, options)
+  .into_iter()
+  .map(|(lonlat, span)| ExtractedLonLat { lonlat, span })
+  .collect()
+}
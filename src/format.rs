@@ -0,0 +1,326 @@
+use crate::{
+ dimensions::{
+  round_to_units,
+  units_per_degree,
+  AngleRange
+ },
+ prelude::*
+};
+use arithmetic_sign::prelude::*;
+use measurements::Angle;
+
+/// The decimal places of the degrees in a GeoURI; 1.0e-9 [deg] ≈ 0.1 [mm]
+pub const GEO_URI_DECIMALS: usize = 9;
+/// The decimal places of the altitude [m] in a GeoURI
+pub const GEO_URI_ALT_DECIMALS: usize = 3;
+
+/// The notation of an angle.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum CoordinateStyle
+{
+ /// Decimal degrees; eg, 42.826667°
+ DecimalDegrees,
+ /// Degrees and decimal minutes(DDM); eg, 42°49.600’
+ DegreesDecimalMinutes,
+ /// Degrees, minutes and seconds(DMS); eg, 42°49’36.0”
+ DegreesMinutesSeconds
+}
+
+/// The unit symbols of the degrees, the minutes and the seconds.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum CoordinateSymbols
+{
+ /// ° ' "
+ Ascii,
+ /// ° ′ ″; ISO 80000-1
+ Prime,
+ /// ° ’ ”
+ Typographic,
+ /// No symbols; eg, 42.826667 for the decimal degrees
+ None,
+ /// The units, the hemispheres and the decimal separator of the locale; eg, `CoordinateSymbols::Locale(&KoKr)`
+ Locale(&'static dyn Locale)
+}
+
+/// The notation of the sign of an angle.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum HemisphereNotation
+{
+ /// "-" for the negatives only
+ Sign,
+ /// "+" or "-"
+ SignAlways,
+ /// N, S, E or W after the value
+ Suffix,
+ /// N, S, E or W before the value
+ Prefix
+}
+
+/// The order of the axes in a formatted coordinate.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum AxisOrder
+{
+ /// Latitude first; eg, ISO 6709 and GeoURI
+ LatLon,
+ /// Longitude first; eg, GeoJSON
+ LonLat
+}
+
+/// A builder of the string notation of the coordinates.
+///
+/// The default is the notation of `ToStringDms`; eg, `+42°49’36.0” +140°48’41.0”`.
+///
+/// Note: The value is rounded at the last decimal place before it is split into the components,
+///       thus 59.96” never appears as 60.0”.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordinateFormatter
+{
+ style: CoordinateStyle,
+ decimals: usize,
+ zero_padding: bool,
+ trim_zeros: bool,
+ symbols: CoordinateSymbols,
+ hemisphere: HemisphereNotation,
+ order: AxisOrder,
+ separator: String
+}
+
+impl Default for CoordinateFormatter
+{
+ fn default() -> Self
+ {
+  Self {
+   style: CoordinateStyle::DegreesMinutesSeconds,
+   decimals: 1,
+   zero_padding: false,
+   trim_zeros: false,
+   symbols: CoordinateSymbols::Typographic,
+   hemisphere: HemisphereNotation::SignAlways,
+   order: AxisOrder::LatLon,
+   separator: " ".into()
+  }
+ }
+}
+
+impl CoordinateFormatter
+{
+ pub fn new() -> Self
+ {
+  Self::default()
+ }
+
+ pub fn style(mut self, style: CoordinateStyle) -> Self
+ {
+  self.style = style;
+  self
+ }
+
+ /// The decimal places of the last component; the degrees, the minutes or the seconds
+ ///
+ /// Note: Up to `ANGLE_MAXIMUM_DECIMALS`; `format` returns `DecimalsOutOfRange` above it.
+ pub fn decimals(mut self, decimals: usize) -> Self
+ {
+  self.decimals = decimals;
+  self
+ }
+
+ /// Pad the integer parts with zeros; 2 digits for the latitude degrees, 3 for the longitude, 2 for the minutes and the seconds
+ pub fn zero_padding(mut self, zero_padding: bool) -> Self
+ {
+  self.zero_padding = zero_padding;
+  self
+ }
+
+ /// Remove the trailing zeros of the decimal places; eg, 42.826667000 -> 42.826667
+ pub fn trim_zeros(mut self, trim_zeros: bool) -> Self
+ {
+  self.trim_zeros = trim_zeros;
+  self
+ }
+
+ pub fn symbols(mut self, symbols: CoordinateSymbols) -> Self
+ {
+  self.symbols = symbols;
+  self
+ }
+
+ pub fn hemisphere(mut self, hemisphere: HemisphereNotation) -> Self
+ {
+  self.hemisphere = hemisphere;
+  self
+ }
+
+ pub fn order(mut self, order: AxisOrder) -> Self
+ {
+  self.order = order;
+  self
+ }
+
+ pub fn separator(mut self, separator: &str) -> Self
+ {
+  self.separator = separator.into();
+  self
+ }
+
+ /// The symbols of the locale, and the hemispheres before or after the value as the locale; eg, `&JaJp` -> "北緯43度3分43.5秒"
+ pub fn locale(mut self, locale: &'static dyn Locale) -> Self
+ {
+  self.symbols = CoordinateSymbols::Locale(locale);
+  self.hemisphere = if locale.hemisphere_before() { HemisphereNotation::Prefix } else { HemisphereNotation::Suffix };
+  self
+ }
+
+ /// return the latitude := [-90..90] as a string
+ pub fn format_lat(&self, lat: Angle) -> Result<String, CivilEngineeringLocationError>
+ {
+  let (sign, total) = round_to_units(lat, AngleRange::Latitude90, self.units_per_degree()?)?;
+  Ok(self.format_units(sign, total, 2, self.symbols.ns()))
+ }
+
+ /// return the longitude := [-180..180) as a string; +180° is the same meridian as -180°, eg, "-180°0’0.0”"
+ pub fn format_lon(&self, lon: Angle) -> Result<String, CivilEngineeringLocationError>
+ {
+  let (sign, total) = round_to_units(lon, AngleRange::Longitude180, self.units_per_degree()?)?;
+  Ok(self.format_units(sign, total, 3, self.symbols.ew()))
+ }
+
+ pub fn format<T: LonLatGettable>(&self, source: &T) -> Result<String, CivilEngineeringLocationError>
+ {
+  let lat = self.format_lat(source.get_lat())?;
+  let lon = self.format_lon(source.get_lon())?;
+  Ok(match self.order
+  {
+   AxisOrder::LatLon => format!("{}{}{}", lat, self.separator, lon),
+   AxisOrder::LonLat => format!("{}{}{}", lon, self.separator, lat)
+  })
+ }
+
+ /// return the units of the last decimal place per degree; an error if the decimals are more than `ANGLE_MAXIMUM_DECIMALS`
+ fn units_per_degree(&self) -> Result<u64, CivilEngineeringLocationError>
+ {
+  let units = match self.style
+  {
+   CoordinateStyle::DecimalDegrees => 1,
+   CoordinateStyle::DegreesDecimalMinutes => 60,
+   CoordinateStyle::DegreesMinutesSeconds => 60 * 60
+  };
+  units_per_degree(units, self.decimals)
+ }
+
+ /// total := |angle| in the units of the last decimal place; the carries are exact in the integers.
+ /// hemispheres := ( negative, positive )
+ fn format_units(&self, sign: Sign, total: u64, degrees_width: usize, hemispheres: (&str, &str)) -> String
+ {
+  let scale = 10u64.pow(self.decimals as u32);
+  let is_negative = sign == Sign::Negative;
+  let (degrees_symbol, minutes_symbol, seconds_symbol) = self.symbols.units();
+
+  let width = |w: usize| if self.zero_padding { w } else { 1 };
+  let body = match self.style
+  {
+   CoordinateStyle::DecimalDegrees => format!("{}{}", self.decimal(total, scale, width(degrees_width)), degrees_symbol),
+   CoordinateStyle::DegreesDecimalMinutes =>
+   {
+    let per_degree = 60 * scale;
+    format!(
+     "{:0w$}{}{}{}",
+     total / per_degree,
+     degrees_symbol,
+     self.decimal(total % per_degree, scale, width(2)),
+     minutes_symbol,
+     w = width(degrees_width)
+    )
+   },
+   CoordinateStyle::DegreesMinutesSeconds =>
+   {
+    let (per_degree, per_minute) = (60 * 60 * scale, 60 * scale);
+    format!(
+     "{:0w$}{}{:0m$}{}{}{}",
+     total / per_degree,
+     degrees_symbol,
+     total % per_degree / per_minute,
+     minutes_symbol,
+     self.decimal(total % per_minute, scale, width(2)),
+     seconds_symbol,
+     w = width(degrees_width),
+     m = width(2)
+    )
+   },
+  };
+
+  let hemisphere = if is_negative { hemispheres.0 } else { hemispheres.1 };
+  match self.hemisphere
+  {
+   HemisphereNotation::Sign => format!("{}{}", if is_negative { "-" } else { "" }, body),
+   HemisphereNotation::SignAlways => format!("{}{}", if is_negative { "-" } else { "+" }, body),
+   HemisphereNotation::Suffix => format!("{}{}", body, hemisphere),
+   HemisphereNotation::Prefix => format!("{}{}", hemisphere, body)
+  }
+ }
+
+ /// return "{value / scale}.{value % scale}" with the decimal places
+ fn decimal(&self, value: u64, scale: u64, width: usize) -> String
+ {
+  let integer = format!("{:0w$}", value / scale, w = width);
+  if self.decimals == 0
+  {
+   return integer;
+  }
+  let fraction = format!("{:0d$}", value % scale, d = self.decimals);
+  let fraction = if self.trim_zeros { fraction.trim_end_matches('0') } else { &fraction[..] };
+  match fraction.is_empty()
+  {
+   true => integer,
+   false => format!("{}{}{}", integer, self.symbols.decimal_separator(), fraction)
+  }
+ }
+}
+
+impl CoordinateSymbols
+{
+ /// return ( degrees, minutes, seconds )
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  match self
+  {
+   CoordinateSymbols::Ascii => ("°", "'", "\""),
+   CoordinateSymbols::Prime => ("°", "′", "″"),
+   CoordinateSymbols::Typographic => ("°", "’", "”"),
+   CoordinateSymbols::None => ("", "", ""),
+   CoordinateSymbols::Locale(locale) => locale.units()
+  }
+ }
+
+ /// return ( south, north )
+ fn ns(&self) -> (&'static str, &'static str)
+ {
+  let (north, south, _, _) = self.hemispheres();
+  (south, north)
+ }
+
+ /// return ( west, east )
+ fn ew(&self) -> (&'static str, &'static str)
+ {
+  let (_, _, east, west) = self.hemispheres();
+  (west, east)
+ }
+
+ /// return ( north, south, east, west )
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  match self
+  {
+   CoordinateSymbols::Locale(locale) => locale.hemispheres(),
+   _ => En.hemispheres()
+  }
+ }
+
+ fn decimal_separator(&self) -> char
+ {
+  match self
+  {
+   CoordinateSymbols::Locale(locale) => locale.decimal_separator(),
+   _ => '.'
+  }
+ }
+}
@@ -0,0 +1,233 @@
+use crate::prelude::*;
+use measurements::{
+ Angle,
+ Length
+};
+
+/// Vincenty's formulae iteration limit
+pub const GEODESIC_MAXIMUM_ITERATIONS: usize = 200;
+/// Vincenty's formulae convergence threshold [rad]; 1.0e-12 [rad] ≈ 0.006 [mm]
+pub const GEODESIC_CONVERGENCE_THRESHOLD: f64 = 1.0e-12;
+
+/// The solution of the geodesic inverse problem.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GeodesicInverse
+{
+ pub distance: Length,
+ /// Azimuth at the start point; clockwise from the north
+ pub initial_azimuth: Angle,
+ /// Azimuth at the end point; clockwise from the north
+ pub final_azimuth: Angle
+}
+
+/// The solution of the geodesic direct problem.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GeodesicDirect
+{
+ pub destination: LonLat,
+ /// Azimuth at the destination; clockwise from the north
+ pub final_azimuth: Angle
+}
+
+/// Geodesics on an ellipsoid; Vincenty's formulae.
+pub trait Geodesic: LonLatGettable
+{
+ /// Note: May return `GeodesicDidNotConverge` for nearly antipodal points.
+ fn geodesic_inverse<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Result<GeodesicInverse, CivilEngineeringLocationError>;
+ fn geodesic_direct(&self, azimuth: Angle, distance: Length, ellipsoid: &Ellipsoid) -> GeodesicDirect;
+
+ fn geodesic_distance<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Result<Length, CivilEngineeringLocationError>
+ {
+  self.geodesic_inverse(target, ellipsoid).map(|i| i.distance)
+ }
+
+ fn geodesic_destination(&self, azimuth: Angle, distance: Length, ellipsoid: &Ellipsoid) -> LonLat
+ {
+  self.geodesic_direct(azimuth, distance, ellipsoid).destination
+ }
+}
+
+/// Great-circles on a sphere; `radius` is the radius of the sphere.
+pub trait GreatCircle: LonLatGettable
+{
+ /// return central angle [rad]; haversine formula
+ fn great_circle_central_angle<T: LonLatGettable>(&self, target: &T) -> f64
+ {
+  let (lat1, lat2) = (self.get_lat().as_radians(), target.get_lat().as_radians());
+  let dlat = lat2 - lat1;
+  let dlon = target.get_lon().as_radians() - self.get_lon().as_radians();
+  let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+  2.0 * h.sqrt().min(ONE).asin()
+ }
+
+ fn great_circle_distance<T: LonLatGettable>(&self, target: &T, radius: Length) -> Length
+ {
+  Length::from_meters(self.great_circle_central_angle(target) * radius.as_meters())
+ }
+
+ /// return initial bearing := [-π..π); clockwise from the north
+ fn great_circle_initial_bearing<T: LonLatGettable>(&self, target: &T) -> Angle
+ {
+  let (lat1, lat2) = (self.get_lat().as_radians(), target.get_lat().as_radians());
+  let dlon = target.get_lon().as_radians() - self.get_lon().as_radians();
+  let y = dlon.sin() * lat2.cos();
+  let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+  Angle::from_radians(y.atan2(x)).as_normalize_negative_pi_positive_pi()
+ }
+
+ fn great_circle_destination(&self, bearing: Angle, distance: Length, radius: Length) -> LonLat
+ {
+  let delta = distance.as_meters() / radius.as_meters();
+  let lat1 = self.get_lat().as_radians();
+  let theta = bearing.as_radians();
+  let lat2 = (lat1.sin() * delta.cos() + lat1.cos() * delta.sin() * theta.cos()).clamp(-ONE, ONE).asin();
+  let lon2 = self.get_lon().as_radians()
+   + (theta.sin() * delta.sin() * lat1.cos()).atan2(delta.cos() - lat1.sin() * lat2.sin());
+  LonLat::new(Angle::from_radians(lon2).as_normalize_negative_pi_positive_pi(), Angle::from_radians(lat2))
+ }
+}
+
+impl<T: LonLatGettable> GreatCircle for T {}
+
+impl<T: LonLatGettable> Geodesic for T
+{
+ fn geodesic_inverse<U: LonLatGettable>(&self, target: &U, ellipsoid: &Ellipsoid) -> Result<GeodesicInverse, CivilEngineeringLocationError>
+ {
+  let (a, f, b) = (ellipsoid.a, ellipsoid.f, ellipsoid.b());
+
+  let lat1 = self.get_lat().as_radians();
+  let lat2 = target.get_lat().as_radians();
+  let l = (target.get_lon() - self.get_lon()).as_normalize_negative_pi_positive_pi().as_radians();
+
+  let u1 = ((ONE - f) * lat1.tan()).atan();
+  let u2 = ((ONE - f) * lat2.tan()).atan();
+  let (sin_u1, cos_u1) = u1.sin_cos();
+  let (sin_u2, cos_u2) = u2.sin_cos();
+
+  let mut lambda = l;
+  let mut converged = false;
+  let (mut sin_sigma, mut cos_sigma, mut sigma) = (ZERO, ONE, ZERO);
+  let (mut cos2_alpha, mut cos_2sigma_m) = (ONE, ONE);
+  let (mut sin_lambda, mut cos_lambda) = (ZERO, ONE);
+
+  for _ in 0..GEODESIC_MAXIMUM_ITERATIONS
+  {
+   let sc = lambda.sin_cos();
+   sin_lambda = sc.0;
+   cos_lambda = sc.1;
+   sin_sigma = ((cos_u2 * sin_lambda).powi(2) + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2)).sqrt();
+   if sin_sigma == ZERO
+   {
+    // Coincident points
+    return Ok(GeodesicInverse {
+     distance: Length::from_meters(ZERO),
+     initial_azimuth: Angle::from_radians(ZERO),
+     final_azimuth: Angle::from_radians(ZERO)
+    });
+   }
+   cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+   sigma = sin_sigma.atan2(cos_sigma);
+   let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+   cos2_alpha = ONE - sin_alpha * sin_alpha;
+   // Note: cos2_alpha = 0 on the equatorial line
+   cos_2sigma_m = if cos2_alpha != ZERO { cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha } else { ZERO };
+   let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+   let lambda_previous = lambda;
+   lambda = l
+    + (ONE - c)
+     * f
+     * sin_alpha
+     * (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-ONE + 2.0 * cos_2sigma_m * cos_2sigma_m)));
+   if lambda.abs() > PI
+   {
+    break;
+   }
+   if (lambda - lambda_previous).abs() < GEODESIC_CONVERGENCE_THRESHOLD
+   {
+    converged = true;
+    break;
+   }
+  }
+
+  if !converged
+  {
+   Err(CivilEngineeringLocationError::GeodesicDidNotConverge)?;
+  }
+
+  let u_sq = cos2_alpha * (a * a - b * b) / (b * b);
+  let aa = ONE + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+  let bb = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+  let delta_sigma = bb
+   * sin_sigma
+   * (cos_2sigma_m
+    + bb / 4.0
+     * (cos_sigma * (-ONE + 2.0 * cos_2sigma_m * cos_2sigma_m)
+      - bb / 6.0 * cos_2sigma_m * (-3.0 + 4.0 * sin_sigma * sin_sigma) * (-3.0 + 4.0 * cos_2sigma_m * cos_2sigma_m)));
+
+  let s = b * aa * (sigma - delta_sigma);
+  let alpha1 = (cos_u2 * sin_lambda).atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+  let alpha2 = (cos_u1 * sin_lambda).atan2(-sin_u1 * cos_u2 + cos_u1 * sin_u2 * cos_lambda);
+
+  Ok(GeodesicInverse {
+   distance: Length::from_meters(s),
+   initial_azimuth: Angle::from_radians(alpha1),
+   final_azimuth: Angle::from_radians(alpha2)
+  })
+ }
+
+ fn geodesic_direct(&self, azimuth: Angle, distance: Length, ellipsoid: &Ellipsoid) -> GeodesicDirect
+ {
+  let (a, f, b) = (ellipsoid.a, ellipsoid.f, ellipsoid.b());
+  let s = distance.as_meters();
+
+  let lat1 = self.get_lat().as_radians();
+  let (sin_alpha1, cos_alpha1) = azimuth.as_radians().sin_cos();
+
+  let tan_u1 = (ONE - f) * lat1.tan();
+  let cos_u1 = ONE / (ONE + tan_u1 * tan_u1).sqrt();
+  let sin_u1 = tan_u1 * cos_u1;
+  let sigma1 = tan_u1.atan2(cos_alpha1);
+  let sin_alpha = cos_u1 * sin_alpha1;
+  let cos2_alpha = ONE - sin_alpha * sin_alpha;
+
+  let u_sq = cos2_alpha * (a * a - b * b) / (b * b);
+  let aa = ONE + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+  let bb = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+
+  let mut sigma = s / (b * aa);
+  let (mut sin_sigma, mut cos_sigma, mut cos_2sigma_m) = (ZERO, ONE, ONE);
+  for _ in 0..GEODESIC_MAXIMUM_ITERATIONS
+  {
+   cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+   let sc = sigma.sin_cos();
+   sin_sigma = sc.0;
+   cos_sigma = sc.1;
+   let delta_sigma = bb
+    * sin_sigma
+    * (cos_2sigma_m
+     + bb / 4.0
+      * (cos_sigma * (-ONE + 2.0 * cos_2sigma_m * cos_2sigma_m)
+       - bb / 6.0 * cos_2sigma_m * (-3.0 + 4.0 * sin_sigma * sin_sigma) * (-3.0 + 4.0 * cos_2sigma_m * cos_2sigma_m)));
+   let sigma_previous = sigma;
+   sigma = s / (b * aa) + delta_sigma;
+   if (sigma - sigma_previous).abs() < GEODESIC_CONVERGENCE_THRESHOLD
+   {
+    break;
+   }
+  }
+
+  let x = sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1;
+  let lat2 = (sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1).atan2((ONE - f) * (sin_alpha * sin_alpha + x * x).sqrt());
+  let lambda = (sin_sigma * sin_alpha1).atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1);
+  let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+  let l = lambda
+   - (ONE - c) * f * sin_alpha * (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-ONE + 2.0 * cos_2sigma_m * cos_2sigma_m)));
+  let lon2 = Angle::from_radians(self.get_lon().as_radians() + l).as_normalize_negative_pi_positive_pi();
+  let alpha2 = sin_alpha.atan2(-x);
+
+  GeodesicDirect {
+   destination: LonLat::new(lon2, Angle::from_radians(lat2)),
+   final_azimuth: Angle::from_radians(alpha2)
+  }
+ }
+}
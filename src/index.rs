@@ -0,0 +1,383 @@
+use crate::prelude::*;
+use measurements::Length;
+use std::{
+ cmp::Ordering,
+ collections::BinaryHeap
+};
+
+/// The maximum number of the entries in a leaf node
+pub const LONLAT_INDEX_LEAF_SIZE: usize = 16;
+
+/// An in-memory spatial index; a bounding volume hierarchy of the unit normal vectors(n-vectors).
+///
+/// - The tree is pruned with the lower bound of the geodesic distance; central angle × the smallest radius of curvature.
+/// - The distances of the results are the geodesic distances on the ellipsoid.
+///   Note: The great-circle distance on the mean radius is used for the nearly antipodal pairs where Vincenty's formulae do not converge.
+#[derive(Debug, Clone)]
+pub struct LonLatIndex<T>
+{
+ entries: Vec<(T, [f64; 3])>,
+ nodes: Vec<Node>,
+ ellipsoid: Ellipsoid
+}
+
+#[derive(Debug, Clone)]
+struct Node
+{
+ min: [f64; 3],
+ max: [f64; 3],
+ begin: usize,
+ end: usize,
+ children: Option<(usize, usize)>
+}
+
+impl<T: LonLatGettable> LonLatIndex<T>
+{
+ pub fn new(items: Vec<T>, ellipsoid: Ellipsoid) -> Self
+ {
+  let mut entries: Vec<(T, [f64; 3])> = items
+   .into_iter()
+   .map(|item| {
+    let n = n_vector(&item);
+    (item, n)
+   })
+   .collect();
+  let mut nodes = Vec::with_capacity(entries.len() * 2 / LONLAT_INDEX_LEAF_SIZE + 1);
+  if !entries.is_empty()
+  {
+   let len = entries.len();
+   build(&mut entries, &mut nodes, 0, len);
+  }
+  Self {
+   entries,
+   nodes,
+   ellipsoid
+  }
+ }
+
+ pub fn len(&self) -> usize
+ {
+  self.entries.len()
+ }
+
+ pub fn is_empty(&self) -> bool
+ {
+  self.entries.is_empty()
+ }
+
+ pub fn ellipsoid(&self) -> &Ellipsoid
+ {
+  &self.ellipsoid
+ }
+
+ pub fn iter(&self) -> impl Iterator<Item = &T>
+ {
+  self.entries.iter().map(|(item, _)| item)
+ }
+
+ /// return the items within the radius of the target, nearest first
+ pub fn within_radius<U: LonLatGettable>(&self, target: &U, radius: Length) -> Vec<(&T, Length)>
+ {
+  let q = n_vector(target);
+  let radius = radius.as_meters();
+  let maximum_angle = radius / self.ellipsoid.minimum_radius_of_curvature();
+  let maximum_chord = angle_to_chord(maximum_angle);
+
+  let mut found = Vec::new();
+  let mut stack = if self.nodes.is_empty() { vec![] } else { vec![0] };
+  while let Some(index) = stack.pop()
+  {
+   let node = &self.nodes[index];
+   if box_distance(&q, node) > maximum_chord
+   {
+    continue;
+   }
+   match node.children
+   {
+    Some((left, right)) =>
+    {
+     stack.push(left);
+     stack.push(right);
+    },
+    None =>
+    {
+     for (item, n) in &self.entries[node.begin..node.end]
+     {
+      if chord(&q, n) <= maximum_chord
+      {
+       let distance = self.distance(target, item);
+       if distance <= radius
+       {
+        found.push((item, Length::from_meters(distance)));
+       }
+      }
+     }
+    },
+   }
+  }
+
+  found.sort_by(|a, b| a.1.as_meters().partial_cmp(&b.1.as_meters()).unwrap_or(Ordering::Equal));
+  found
+ }
+
+ /// return the k-nearest items of the target, nearest first
+ pub fn nearest<U: LonLatGettable>(&self, target: &U, k: usize) -> Vec<(&T, Length)>
+ {
+  if k == 0 || self.nodes.is_empty()
+  {
+   return vec![];
+  }
+
+  let q = n_vector(target);
+  let r_min = self.ellipsoid.minimum_radius_of_curvature();
+
+  // nodes: nearest lower bound first, found: farthest first
+  let mut nodes = BinaryHeap::new();
+  let mut found: BinaryHeap<Nearest<usize>> = BinaryHeap::with_capacity(k + 1);
+  nodes.push(Nearest {
+   distance: -chord_to_angle(box_distance(&q, &self.nodes[0])) * r_min,
+   value: 0
+  });
+
+  while let Some(Nearest {
+   distance,
+   value: index
+  }) = nodes.pop()
+  {
+   if found.len() == k && -distance > found.peek().map(|f| f.distance).unwrap_or(f64::INFINITY)
+   {
+    break;
+   }
+   let node = &self.nodes[index];
+   match node.children
+   {
+    Some((left, right)) =>
+    {
+     for &child in &[left, right]
+     {
+      nodes.push(Nearest {
+       distance: -chord_to_angle(box_distance(&q, &self.nodes[child])) * r_min,
+       value: child
+      });
+     }
+    },
+    None =>
+    {
+     for entry in node.begin..node.end
+     {
+      let (item, n) = &self.entries[entry];
+      let lower_bound = chord_to_angle(chord(&q, n)) * r_min;
+      if found.len() == k && lower_bound > found.peek().map(|f| f.distance).unwrap_or(f64::INFINITY)
+      {
+       continue;
+      }
+      found.push(Nearest {
+       distance: self.distance(target, item),
+       value: entry
+      });
+      if found.len() > k
+      {
+       found.pop();
+      }
+     }
+    },
+   }
+  }
+
+  let mut found: Vec<(&T, Length)> = found
+   .into_iter()
+   .map(|f| (&self.entries[f.value].0, Length::from_meters(f.distance)))
+   .collect();
+  found.sort_by(|a, b| a.1.as_meters().partial_cmp(&b.1.as_meters()).unwrap_or(Ordering::Equal));
+  found
+ }
+
+ /// return the items within the bounds
+ pub fn within_bounds(&self, bounds: &LonLatBounds) -> Vec<&T>
+ {
+  let (min, max) = bounds_box(bounds);
+
+  let mut found = Vec::new();
+  let mut stack = if self.nodes.is_empty() { vec![] } else { vec![0] };
+  while let Some(index) = stack.pop()
+  {
+   let node = &self.nodes[index];
+   if (0..3).any(|axis| node.max[axis] < min[axis] || node.min[axis] > max[axis])
+   {
+    continue;
+   }
+   match node.children
+   {
+    Some((left, right)) =>
+    {
+     stack.push(left);
+     stack.push(right);
+    },
+    None => found.extend(self.entries[node.begin..node.end].iter().filter(|(item, _)| bounds.contains(item)).map(|(item, _)| item))
+   }
+  }
+  found
+ }
+
+ fn distance<A: LonLatGettable, B: LonLatGettable>(&self, a: &A, b: &B) -> f64
+ {
+  match a.geodesic_distance(b, &self.ellipsoid)
+  {
+   Ok(distance) => distance.as_meters(),
+   Err(_) => a.great_circle_central_angle(b) * self.ellipsoid.mean_radius()
+  }
+ }
+}
+
+impl<T: LonLatGettable> std::iter::FromIterator<T> for LonLatIndex<T>
+{
+ /// Note: The ellipsoid is WGS84.
+ fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self
+ {
+  Self::new(iter.into_iter().collect(), Ellipsoid::WGS84)
+ }
+}
+
+/// A heap entry ordered by the distance; `BinaryHeap` pops the largest first.
+struct Nearest<V>
+{
+ distance: f64,
+ value: V
+}
+
+impl<V> PartialEq for Nearest<V>
+{
+ fn eq(&self, other: &Self) -> bool
+ {
+  self.cmp(other) == Ordering::Equal
+ }
+}
+
+impl<V> Eq for Nearest<V> {}
+
+impl<V> PartialOrd for Nearest<V>
+{
+ fn partial_cmp(&self, other: &Self) -> Option<Ordering>
+ {
+  Some(self.cmp(other))
+ }
+}
+
+impl<V> Ord for Nearest<V>
+{
+ fn cmp(&self, other: &Self) -> Ordering
+ {
+  self.distance.partial_cmp(&other.distance).unwrap_or(Ordering::Equal)
+ }
+}
+
+/// The unit vector of the normal to the sphere at the point; ECEF-like ( x, y, z )
+pub(crate) fn n_vector<T: LonLatGettable>(source: &T) -> [f64; 3]
+{
+ let (sin_lat, cos_lat) = source.get_lat().as_radians().sin_cos();
+ let (sin_lon, cos_lon) = source.get_lon().as_radians().sin_cos();
+ [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat]
+}
+
+fn chord(a: &[f64; 3], b: &[f64; 3]) -> f64
+{
+ ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+fn angle_to_chord(angle: f64) -> f64
+{
+ if angle >= PI
+ {
+  2.0
+ }
+ else
+ {
+  2.0 * (angle / 2.0).sin()
+ }
+}
+
+fn chord_to_angle(chord: f64) -> f64
+{
+ 2.0 * (chord / 2.0).min(ONE).asin()
+}
+
+/// return the distance from the point to the bounding box of the node
+fn box_distance(q: &[f64; 3], node: &Node) -> f64
+{
+ (0..3)
+  .map(|axis| (node.min[axis] - q[axis]).max(q[axis] - node.max[axis]).max(ZERO).powi(2))
+  .sum::<f64>()
+  .sqrt()
+}
+
+/// return the bounding box of the n-vectors in the bounds
+fn bounds_box(bounds: &LonLatBounds) -> ([f64; 3], [f64; 3])
+{
+ let (south, north) = (bounds.south.as_radians(), bounds.north.as_radians());
+ let cos_lat_min = south.cos().min(north.cos());
+ let cos_lat_max = if south <= ZERO && north >= ZERO { ONE } else { south.cos().max(north.cos()) };
+
+ // The range of cos/sin of the longitude over the arc
+ let west = bounds.west.as_radians();
+ let width = bounds.width().as_radians();
+ let contains = |lon: f64| (lon - west).rem_euclid(PI_MUL_2) <= width;
+ let range = |f: fn(f64) -> f64, max_at: f64, min_at: f64| {
+  let ends = [f(west), f(west + width)];
+  let max = if contains(max_at) { ONE } else { ends[0].max(ends[1]) };
+  let min = if contains(min_at) { -ONE } else { ends[0].min(ends[1]) };
+  (min, max)
+ };
+ let (cos_lon_min, cos_lon_max) = range(f64::cos, ZERO, PI);
+ let (sin_lon_min, sin_lon_max) = range(f64::sin, PI_DIV_2, -PI_DIV_2);
+
+ // The product of the ranges; cos(lat) >= 0
+ let product = |min: f64, max: f64| {
+  let candidates = [min * cos_lat_min, min * cos_lat_max, max * cos_lat_min, max * cos_lat_max];
+  (
+   candidates.iter().cloned().fold(f64::INFINITY, f64::min),
+   candidates.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+  )
+ };
+ let (x_min, x_max) = product(cos_lon_min, cos_lon_max);
+ let (y_min, y_max) = product(sin_lon_min, sin_lon_max);
+
+ ([x_min, y_min, south.sin()], [x_max, y_max, north.sin()])
+}
+
+fn build<T>(entries: &mut [(T, [f64; 3])], nodes: &mut Vec<Node>, begin: usize, end: usize) -> usize
+{
+ let mut min = [f64::INFINITY; 3];
+ let mut max = [f64::NEG_INFINITY; 3];
+ for (_, n) in &entries[begin..end]
+ {
+  for axis in 0..3
+  {
+   min[axis] = min[axis].min(n[axis]);
+   max[axis] = max[axis].max(n[axis]);
+  }
+ }
+
+ let index = nodes.len();
+ nodes.push(Node {
+  min,
+  max,
+  begin,
+  end,
+  children: None
+ });
+
+ if end - begin > LONLAT_INDEX_LEAF_SIZE
+ {
+  // Split at the median of the longest axis
+  let axis = (0..3)
+   .max_by(|&a, &b| (max[a] - min[a]).partial_cmp(&(max[b] - min[b])).unwrap_or(Ordering::Equal))
+   .unwrap_or(0);
+  let middle = begin + (end - begin) / 2;
+  entries[begin..end].select_nth_unstable_by(middle - begin, |a, b| a.1[axis].partial_cmp(&b.1[axis]).unwrap_or(Ordering::Equal));
+  let left = build(entries, nodes, begin, middle);
+  let right = build(entries, nodes, middle, end);
+  nodes[index].children = Some((left, right));
+ }
+
+ index
+}
@@ -0,0 +1,189 @@
+use crate::{
+ index::n_vector,
+ prelude::*
+};
+use measurements::{
+ Angle,
+ Length
+};
+
+/// The central angle [rad] within which the points are antipodal for the great-circle interpolation
+pub const GREAT_CIRCLE_ANTIPODAL_THRESHOLD: f64 = 1.0e-12;
+
+/// Intermediate points along the geodesic on an ellipsoid.
+pub trait GeodesicInterpolation: LonLatGettable
+{
+ /// return the point at the fraction of the geodesic; 0 := self, 1 := target
+ fn geodesic_intermediate<T: LonLatGettable>(
+  &self,
+  target: &T,
+  fraction: f64,
+  ellipsoid: &Ellipsoid
+ ) -> Result<LonLat, CivilEngineeringLocationError>;
+
+ fn geodesic_midpoint<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Result<LonLat, CivilEngineeringLocationError>
+ {
+  self.geodesic_intermediate(target, 0.5, ellipsoid)
+ }
+
+ /// return `segments + 1` evenly spaced points including the both ends
+ fn geodesic_densify<T: LonLatGettable>(
+  &self,
+  target: &T,
+  segments: usize,
+  ellipsoid: &Ellipsoid
+ ) -> Result<Vec<LonLat>, CivilEngineeringLocationError>;
+}
+
+/// Intermediate points along the great-circle on a sphere.
+pub trait GreatCircleInterpolation: LonLatGettable
+{
+ /// return the point at the fraction of the great-circle; 0 := self, 1 := target
+ ///
+ /// Note: `GreatCircleAntipodal` for the (nearly) antipodal points; any great-circle connects them.
+ fn great_circle_intermediate<T: LonLatGettable>(&self, target: &T, fraction: f64) -> Result<LonLat, CivilEngineeringLocationError>;
+
+ fn great_circle_midpoint<T: LonLatGettable>(&self, target: &T) -> Result<LonLat, CivilEngineeringLocationError>
+ {
+  self.great_circle_intermediate(target, 0.5)
+ }
+
+ /// return `segments + 1` evenly spaced points including the both ends
+ fn great_circle_densify<T: LonLatGettable>(&self, target: &T, segments: usize) -> Result<Vec<LonLat>, CivilEngineeringLocationError>
+ {
+  let segments = segments.max(1);
+  (0..=segments).map(|i| self.great_circle_intermediate(target, i as f64 / segments as f64)).collect()
+ }
+}
+
+/// Intermediate points along the geodesic with the linearly interpolated altitude.
+pub trait GeodesicInterpolationWithAlt: LonLatGettable + AltGettable
+{
+ fn geodesic_intermediate_with_alt<T: LonLatGettable + AltGettable>(
+  &self,
+  target: &T,
+  fraction: f64,
+  ellipsoid: &Ellipsoid
+ ) -> Result<LonLatAlt, CivilEngineeringLocationError>;
+
+ fn geodesic_midpoint_with_alt<T: LonLatGettable + AltGettable>(
+  &self,
+  target: &T,
+  ellipsoid: &Ellipsoid
+ ) -> Result<LonLatAlt, CivilEngineeringLocationError>
+ {
+  self.geodesic_intermediate_with_alt(target, 0.5, ellipsoid)
+ }
+
+ /// return `segments + 1` evenly spaced points including the both ends
+ fn geodesic_densify_with_alt<T: LonLatGettable + AltGettable>(
+  &self,
+  target: &T,
+  segments: usize,
+  ellipsoid: &Ellipsoid
+ ) -> Result<Vec<LonLatAlt>, CivilEngineeringLocationError>;
+}
+
+impl<S: LonLatGettable> GeodesicInterpolation for S
+{
+ fn geodesic_intermediate<T: LonLatGettable>(
+  &self,
+  target: &T,
+  fraction: f64,
+  ellipsoid: &Ellipsoid
+ ) -> Result<LonLat, CivilEngineeringLocationError>
+ {
+  let inverse = self.geodesic_inverse(target, ellipsoid)?;
+  Ok(intermediate(self, &inverse, fraction, ellipsoid))
+ }
+
+ fn geodesic_densify<T: LonLatGettable>(
+  &self,
+  target: &T,
+  segments: usize,
+  ellipsoid: &Ellipsoid
+ ) -> Result<Vec<LonLat>, CivilEngineeringLocationError>
+ {
+  let segments = segments.max(1);
+  let inverse = self.geodesic_inverse(target, ellipsoid)?;
+  let mut points: Vec<LonLat> = (0..segments)
+   .map(|i| intermediate(self, &inverse, i as f64 / segments as f64, ellipsoid))
+   .collect();
+  points.push(target.as_lonlat());
+  Ok(points)
+ }
+}
+
+impl<S: LonLatGettable> GreatCircleInterpolation for S
+{
+ fn great_circle_intermediate<T: LonLatGettable>(&self, target: &T, fraction: f64) -> Result<LonLat, CivilEngineeringLocationError>
+ {
+  let (v1, v2) = (n_vector(self), n_vector(target));
+
+  // atan2 of the cross and the dot products; exact near π unlike the haversine
+  let cross = [v1[1] * v2[2] - v1[2] * v2[1], v1[2] * v2[0] - v1[0] * v2[2], v1[0] * v2[1] - v1[1] * v2[0]];
+  let dot = v1[0] * v2[0] + v1[1] * v2[1] + v1[2] * v2[2];
+  let delta = cross[0].hypot(cross[1]).hypot(cross[2]).atan2(dot);
+  if delta == ZERO
+  {
+   return Ok(self.as_lonlat());
+  }
+  if PI - delta <= GREAT_CIRCLE_ANTIPODAL_THRESHOLD
+  {
+   Err(CivilEngineeringLocationError::GreatCircleAntipodal)?;
+  }
+
+  let a = ((ONE - fraction) * delta).sin() / delta.sin();
+  let b = (fraction * delta).sin() / delta.sin();
+  let x = a * v1[0] + b * v2[0];
+  let y = a * v1[1] + b * v2[1];
+  let z = a * v1[2] + b * v2[2];
+
+  Ok(LonLat::new(
+   Angle::from_radians(y.atan2(x)).as_normalize_negative_pi_positive_pi(),
+   Angle::from_radians(z.atan2(x.hypot(y)))
+  ))
+ }
+}
+
+impl<S: LonLatGettable + AltGettable> GeodesicInterpolationWithAlt for S
+{
+ fn geodesic_intermediate_with_alt<T: LonLatGettable + AltGettable>(
+  &self,
+  target: &T,
+  fraction: f64,
+  ellipsoid: &Ellipsoid
+ ) -> Result<LonLatAlt, CivilEngineeringLocationError>
+ {
+  let lonlat = self.geodesic_intermediate(target, fraction, ellipsoid)?;
+  Ok(LonLatAlt::from((lonlat, interpolate_alt(self, target, fraction))))
+ }
+
+ fn geodesic_densify_with_alt<T: LonLatGettable + AltGettable>(
+  &self,
+  target: &T,
+  segments: usize,
+  ellipsoid: &Ellipsoid
+ ) -> Result<Vec<LonLatAlt>, CivilEngineeringLocationError>
+ {
+  let segments = segments.max(1);
+  let points = self.geodesic_densify(target, segments, ellipsoid)?;
+  Ok(points
+   .into_iter()
+   .enumerate()
+   .map(|(i, lonlat)| LonLatAlt::from((lonlat, interpolate_alt(self, target, i as f64 / segments as f64))))
+   .collect())
+ }
+}
+
+fn intermediate<S: LonLatGettable>(source: &S, inverse: &GeodesicInverse, fraction: f64, ellipsoid: &Ellipsoid) -> LonLat
+{
+ let distance = Length::from_meters(inverse.distance.as_meters() * fraction);
+ source.geodesic_destination(inverse.initial_azimuth, distance, ellipsoid)
+}
+
+fn interpolate_alt<S: AltGettable, T: AltGettable>(source: &S, target: &T, fraction: f64) -> Length
+{
+ let (a, b) = (source.get_alt().as_meters(), target.get_alt().as_meters());
+ Length::from_meters(a + (b - a) * fraction)
+}
@@ -0,0 +1,217 @@
+use crate::{
+ index::n_vector,
+ prelude::*
+};
+use measurements::{
+ Angle,
+ Length
+};
+
+/// Threshold of the cross product of the great-circle normals to treat the lines as coincident
+pub const INTERSECTION_COINCIDENT_THRESHOLD: f64 = 1.0e-12;
+/// Convergence threshold [m] of the intersection on an ellipsoid
+pub const INTERSECTION_CONVERGENCE_THRESHOLD: f64 = 1.0e-6;
+
+/// A great-circle or geodesic line.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GeodesicLine
+{
+ /// A line from the origin toward the bearing; clockwise from the north
+ Bearing(LonLat, Angle),
+ /// A line through the two points; the segment is from the first to the second
+ Points(LonLat, LonLat)
+}
+
+impl GeodesicLine
+{
+ pub fn from_bearing<T: LonLatGettable>(origin: &T, bearing: Angle) -> Self
+ {
+  GeodesicLine::Bearing(origin.as_lonlat(), bearing)
+ }
+
+ pub fn from_points<A: LonLatGettable, B: LonLatGettable>(a: &A, b: &B) -> Self
+ {
+  GeodesicLine::Points(a.as_lonlat(), b.as_lonlat())
+ }
+
+ pub fn origin(&self) -> LonLat
+ {
+  match self
+  {
+   GeodesicLine::Bearing(origin, _) | GeodesicLine::Points(origin, _) => *origin
+  }
+ }
+
+ /// return the two intersections of the great-circles on a sphere; the nearer to the origin of `self` first.
+ ///
+ /// Note: The intersections are antipodal.
+ pub fn great_circle_intersections(&self, other: &Self) -> Result<[LonLat; 2], CivilEngineeringLocationError>
+ {
+  let candidates = self.great_circle_candidates(other)?;
+  Ok([to_lonlat(&candidates[0]), to_lonlat(&candidates[1])])
+ }
+
+ /// return the two intersections of the geodesics on an ellipsoid; the nearer to the origin of `self` first.
+ pub fn geodesic_intersections(&self, other: &Self, ellipsoid: &Ellipsoid) -> Result<[LonLat; 2], CivilEngineeringLocationError>
+ {
+  let candidates = self.great_circle_candidates(other)?;
+  let first = self.refine(other, &candidates[0], ellipsoid)?;
+  let second = self.refine(other, &candidates[1], ellipsoid)?;
+  Ok([first.0, second.0])
+ }
+
+ /// return the intersection of the two segments, or `None` if the segments do not cross.
+ ///
+ /// Note: A `Bearing` line is treated as a ray from the origin.
+ pub fn geodesic_segment_intersection(&self, other: &Self, ellipsoid: &Ellipsoid) -> Result<Option<LonLat>, CivilEngineeringLocationError>
+ {
+  let candidates = self.great_circle_candidates(other)?;
+  let (length1, length2) = (self.length(ellipsoid)?, other.length(ellipsoid)?);
+  for candidate in &candidates
+  {
+   let (point, s1, s2) = self.refine(other, candidate, ellipsoid)?;
+   let tolerance = INTERSECTION_CONVERGENCE_THRESHOLD;
+   if s1 >= -tolerance && s1 <= length1 + tolerance && s2 >= -tolerance && s2 <= length2 + tolerance
+   {
+    return Ok(Some(point));
+   }
+  }
+  Ok(None)
+ }
+
+ /// return the length of the segment [m]; infinite for a `Bearing` line
+ fn length(&self, ellipsoid: &Ellipsoid) -> Result<f64, CivilEngineeringLocationError>
+ {
+  match self
+  {
+   GeodesicLine::Bearing(..) => Ok(f64::INFINITY),
+   GeodesicLine::Points(a, b) => a.geodesic_distance(b, ellipsoid).map(|d| d.as_meters())
+  }
+ }
+
+ /// return the azimuth at the origin on the ellipsoid
+ fn azimuth(&self, ellipsoid: &Ellipsoid) -> Result<Angle, CivilEngineeringLocationError>
+ {
+  match self
+  {
+   GeodesicLine::Bearing(_, bearing) => Ok(*bearing),
+   GeodesicLine::Points(a, b) => a.geodesic_inverse(b, ellipsoid).map(|i| i.initial_azimuth)
+  }
+ }
+
+ /// return the unit normal vector of the great-circle
+ fn normal(&self) -> Result<[f64; 3], CivilEngineeringLocationError>
+ {
+  let n = match self
+  {
+   GeodesicLine::Bearing(origin, bearing) =>
+   {
+    let (sin_lat, cos_lat) = origin.lat.as_radians().sin_cos();
+    let (sin_lon, cos_lon) = origin.lon.as_radians().sin_cos();
+    let (sin_b, cos_b) = bearing.as_radians().sin_cos();
+    [sin_lon * cos_b - sin_lat * cos_lon * sin_b, -cos_lon * cos_b - sin_lat * sin_lon * sin_b, cos_lat * sin_b]
+   },
+   GeodesicLine::Points(a, b) => cross(&n_vector(a), &n_vector(b))
+  };
+  normalize(&n).ok_or(CivilEngineeringLocationError::IntersectionUndefinedLine)
+ }
+
+ /// return the two candidates on the unit sphere; the nearer to the origin of `self` first
+ fn great_circle_candidates(&self, other: &Self) -> Result<[[f64; 3]; 2], CivilEngineeringLocationError>
+ {
+  let i = normalize(&cross(&self.normal()?, &other.normal()?)).ok_or(CivilEngineeringLocationError::IntersectionCoincident)?;
+  let antipode = [-i[0], -i[1], -i[2]];
+  let origin = n_vector(&self.origin());
+  if dot(&origin, &i) >= dot(&origin, &antipode)
+  {
+   Ok([i, antipode])
+  }
+  else
+  {
+   Ok([antipode, i])
+  }
+ }
+
+ /// return ( the intersection, distance along `self` [m], distance along `other` [m] ); Newton's method on the local tangent plane
+ fn refine(&self, other: &Self, candidate: &[f64; 3], ellipsoid: &Ellipsoid) -> Result<(LonLat, f64, f64), CivilEngineeringLocationError>
+ {
+  let (origin1, origin2) = (self.origin(), other.origin());
+  let (azimuth1, azimuth2) = (self.azimuth(ellipsoid)?, other.azimuth(ellipsoid)?);
+
+  let radius = ellipsoid.mean_radius();
+  let mut s1 = signed_angle(&origin1, azimuth1, candidate) * radius;
+  let mut s2 = signed_angle(&origin2, azimuth2, candidate) * radius;
+
+  for _ in 0..GEODESIC_MAXIMUM_ITERATIONS
+  {
+   let x1 = origin1.geodesic_direct(azimuth1, Length::from_meters(s1), ellipsoid);
+   let x2 = origin2.geodesic_direct(azimuth2, Length::from_meters(s2), ellipsoid);
+   let between = x1.destination.geodesic_inverse(&x2.destination, ellipsoid)?;
+   let d = between.distance.as_meters();
+   if d < INTERSECTION_CONVERGENCE_THRESHOLD
+   {
+    return Ok((x1.destination, s1, s2));
+   }
+
+   // The azimuth of the other line transported to x1
+   let rotation = (between.final_azimuth - between.initial_azimuth).as_radians();
+   let (u1x, u1y) = x1.final_azimuth.as_radians().sin_cos();
+   let (u2x, u2y) = (x2.final_azimuth.as_radians() - rotation).sin_cos();
+   let (dx, dy) = between.initial_azimuth.as_radians().sin_cos();
+   let (dx, dy) = (dx * d, dy * d);
+
+   // δ1·u1 - δ2·u2 = d
+   let determinant = -u1x * u2y + u2x * u1y;
+   if determinant.abs() < INTERSECTION_COINCIDENT_THRESHOLD
+   {
+    Err(CivilEngineeringLocationError::IntersectionParallel)?;
+   }
+   s1 += (-dx * u2y + u2x * dy) / determinant;
+   s2 += (u1x * dy - u1y * dx) / determinant;
+  }
+
+  Err(CivilEngineeringLocationError::IntersectionParallel)
+ }
+}
+
+fn to_lonlat(n: &[f64; 3]) -> LonLat
+{
+ LonLat::new(Angle::from_radians(n[1].atan2(n[0])), Angle::from_radians(n[2].atan2(n[0].hypot(n[1]))))
+}
+
+fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3]
+{
+ [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn dot(a: &[f64; 3], b: &[f64; 3]) -> f64
+{
+ a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn length(a: &[f64; 3]) -> f64
+{
+ dot(a, a).sqrt()
+}
+
+fn normalize(a: &[f64; 3]) -> Option<[f64; 3]>
+{
+ let l = length(a);
+ match l > INTERSECTION_COINCIDENT_THRESHOLD
+ {
+  true => Some([a[0] / l, a[1] / l, a[2] / l]),
+  false => None
+ }
+}
+
+/// return the signed angle [rad] from the origin to the target along the great-circle of the bearing := [-π..π)
+fn signed_angle(origin: &LonLat, bearing: Angle, target: &[f64; 3]) -> f64
+{
+ let line = GeodesicLine::Bearing(*origin, bearing);
+ let p = n_vector(origin);
+ match line.normal()
+ {
+  Ok(n) => dot(&cross(&p, target), &n).atan2(dot(&p, target)),
+  Err(_) => ZERO
+ }
+}
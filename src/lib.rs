@@ -1,5 +1,23 @@
+pub mod bounds;
+pub mod buffer;
+pub mod compact;
 pub mod error;
+pub mod extract;
+pub mod format;
+pub mod ellipsoid;
+pub mod geodesic;
+pub mod index;
+pub mod interpolation;
+pub mod intersection;
+pub mod locale;
 pub mod location;
 pub mod lonlat;
+pub mod map_url;
+pub mod parse;
+pub mod path;
+pub mod polygon;
+pub mod polyline;
 pub mod prelude;
+pub mod rhumb;
+pub mod simplify;
 pub mod dimensions;
@@ -0,0 +1,371 @@
+use crate::prelude::*;
+
+/// The components of the DMS in the words of the locales; cf. `AngularUnit` for the other angular units.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum DmsComponent
+{
+ Degrees,
+ Minutes,
+ Seconds
+}
+
+/// The words, the symbols and the number format of a language for the notations of the coordinates.
+///
+/// - Formatting: `CoordinateFormatter::locale`; eg, `CoordinateFormatter::new().locale(&JaJp)` -> "北緯43度3分43.5秒 東経141度21分15.8秒"
+/// - Parsing: `ParseOptions::locales`; the locales are usable at the same time, eg, `&[&En, &JaJp]`
+pub trait Locale: std::fmt::Debug + Sync
+{
+ /// The language tag; eg, "ja-JP"
+ fn tag(&self) -> &'static str;
+
+ /// ( degrees, minutes, seconds ); eg, ("度", "分", "秒")
+ fn units(&self) -> (&'static str, &'static str, &'static str);
+
+ /// ( north, south, east, west ); eg, ("北緯", "南緯", "東経", "西経")
+ ///
+ /// Note: The letters may conflict between the locales; eg, "O" of `Fr` and `De`, cf. `ParseOptions::locales`.
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str);
+
+ /// true if the hemisphere is before the value; eg, "北緯43度"
+ fn hemisphere_before(&self) -> bool
+ {
+  false
+ }
+
+ /// eg, ',' of "43°3′43,5″N" in French
+ fn decimal_separator(&self) -> char
+ {
+  '.'
+ }
+
+ /// The other words of the hemispheres to parse; ( word, sign, direction ), case-insensitive
+ fn hemisphere_words(&self) -> &'static [(&'static str, f64, AngleDirectionNotation)]
+ {
+  &[]
+ }
+
+ /// The other words of the units to parse after the spaces; case-insensitive
+ fn unit_words(&self) -> &'static [(&'static str, DmsComponent)]
+ {
+  &[]
+ }
+
+ /// true to normalize the full-width characters and the CJK numerals before parsing; eg, "４３", "四十三"
+ fn normalizes_cjk(&self) -> bool
+ {
+  false
+ }
+}
+
+impl PartialEq for dyn Locale
+{
+ fn eq(&self, other: &Self) -> bool
+ {
+  self.tag() == other.tag()
+ }
+}
+
+impl Eq for dyn Locale {}
+
+/// English; "43°3′43.5″N", "43 deg 3 min 43.5 sec North"
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct En;
+
+/// Japanese; "北緯43度3分43.5秒"
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct JaJp;
+
+/// Simplified Chinese; "北纬43度3分43.5秒"
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct ZhCn;
+
+/// Traditional Chinese; "北緯43度3分43.5秒", "東經141度21分15.8秒"
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct ZhTw;
+
+/// Korean; "북위43도3분43.5초"
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct KoKr;
+
+/// French; "43°3′43,5″N", "141°21′15,8″O" for the west
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct Fr;
+
+/// German; "43°3′43,5″N", "141°21′15,8″O" for the east
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct De;
+
+/// Spanish; "43°3′43,5″N", "141°21′15,8″O" for the west
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub struct Es;
+
+/// All the built-in locales; eg, `std::slice::from_ref(&LOCALES[1])` for `ParseOptions::locales`
+pub static LOCALES: [&dyn Locale; 8] = [&En, &JaJp, &ZhCn, &ZhTw, &KoKr, &Fr, &De, &Es];
+
+/// return the built-in locale of the language tag; case-insensitive, "_" is the same as "-"
+pub fn locale_from_tag(tag: &str) -> Option<&'static dyn Locale>
+{
+ let same = |a: u8, b: u8| a.eq_ignore_ascii_case(&if b == b'_' { b'-' } else { b });
+ LOCALES
+  .iter()
+  .copied()
+  .find(|locale| locale.tag().len() == tag.len() && locale.tag().bytes().zip(tag.bytes()).all(|(a, b)| same(a, b)))
+}
+
+use AngleDirectionNotation::{
+ Latitude,
+ Longitude
+};
+
+impl Locale for En
+{
+ fn tag(&self) -> &'static str
+ {
+  "en"
+ }
+
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  ("°", "′", "″")
+ }
+
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  ("N", "S", "E", "W")
+ }
+
+ fn hemisphere_words(&self) -> &'static [(&'static str, f64, AngleDirectionNotation)]
+ {
+  &[("north", ONE, Latitude), ("south", -ONE, Latitude), ("east", ONE, Longitude), ("west", -ONE, Longitude)]
+ }
+
+ fn unit_words(&self) -> &'static [(&'static str, DmsComponent)]
+ {
+  &[
+   ("degrees", DmsComponent::Degrees),
+   ("degree", DmsComponent::Degrees),
+   ("degs", DmsComponent::Degrees),
+   ("deg", DmsComponent::Degrees),
+   ("minutes", DmsComponent::Minutes),
+   ("minute", DmsComponent::Minutes),
+   ("mins", DmsComponent::Minutes),
+   ("min", DmsComponent::Minutes),
+   ("seconds", DmsComponent::Seconds),
+   ("second", DmsComponent::Seconds),
+   ("secs", DmsComponent::Seconds),
+   ("sec", DmsComponent::Seconds)
+  ]
+ }
+}
+
+impl Locale for JaJp
+{
+ fn tag(&self) -> &'static str
+ {
+  "ja-JP"
+ }
+
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  ("度", "分", "秒")
+ }
+
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  ("北緯", "南緯", "東経", "西経")
+ }
+
+ fn hemisphere_before(&self) -> bool
+ {
+  true
+ }
+
+ fn normalizes_cjk(&self) -> bool
+ {
+  true
+ }
+}
+
+impl Locale for ZhCn
+{
+ fn tag(&self) -> &'static str
+ {
+  "zh-CN"
+ }
+
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  ("度", "分", "秒")
+ }
+
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  ("北纬", "南纬", "东经", "西经")
+ }
+
+ fn hemisphere_before(&self) -> bool
+ {
+  true
+ }
+
+ fn normalizes_cjk(&self) -> bool
+ {
+  true
+ }
+}
+
+impl Locale for ZhTw
+{
+ fn tag(&self) -> &'static str
+ {
+  "zh-TW"
+ }
+
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  ("度", "分", "秒")
+ }
+
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  ("北緯", "南緯", "東經", "西經")
+ }
+
+ fn hemisphere_before(&self) -> bool
+ {
+  true
+ }
+
+ fn normalizes_cjk(&self) -> bool
+ {
+  true
+ }
+}
+
+impl Locale for KoKr
+{
+ fn tag(&self) -> &'static str
+ {
+  "ko-KR"
+ }
+
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  ("도", "분", "초")
+ }
+
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  ("북위", "남위", "동경", "서경")
+ }
+
+ fn hemisphere_before(&self) -> bool
+ {
+  true
+ }
+
+ fn normalizes_cjk(&self) -> bool
+ {
+  true
+ }
+}
+
+impl Locale for Fr
+{
+ fn tag(&self) -> &'static str
+ {
+  "fr"
+ }
+
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  ("°", "′", "″")
+ }
+
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  ("N", "S", "E", "O")
+ }
+
+ fn decimal_separator(&self) -> char
+ {
+  ','
+ }
+
+ fn hemisphere_words(&self) -> &'static [(&'static str, f64, AngleDirectionNotation)]
+ {
+  &[("nord", ONE, Latitude), ("sud", -ONE, Latitude), ("est", ONE, Longitude), ("ouest", -ONE, Longitude)]
+ }
+
+ fn unit_words(&self) -> &'static [(&'static str, DmsComponent)]
+ {
+  &[("degrés", DmsComponent::Degrees), ("degré", DmsComponent::Degrees)]
+ }
+}
+
+impl Locale for De
+{
+ fn tag(&self) -> &'static str
+ {
+  "de"
+ }
+
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  ("°", "′", "″")
+ }
+
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  ("N", "S", "O", "W")
+ }
+
+ fn decimal_separator(&self) -> char
+ {
+  ','
+ }
+
+ fn hemisphere_words(&self) -> &'static [(&'static str, f64, AngleDirectionNotation)]
+ {
+  &[("nord", ONE, Latitude), ("süd", -ONE, Latitude), ("ost", ONE, Longitude), ("west", -ONE, Longitude)]
+ }
+
+ fn unit_words(&self) -> &'static [(&'static str, DmsComponent)]
+ {
+  // "grad" is the gradians in `from_unit_str`, which does not use the locales
+  &[("grad", DmsComponent::Degrees)]
+ }
+}
+
+impl Locale for Es
+{
+ fn tag(&self) -> &'static str
+ {
+  "es"
+ }
+
+ fn units(&self) -> (&'static str, &'static str, &'static str)
+ {
+  ("°", "′", "″")
+ }
+
+ fn hemispheres(&self) -> (&'static str, &'static str, &'static str, &'static str)
+ {
+  ("N", "S", "E", "O")
+ }
+
+ fn decimal_separator(&self) -> char
+ {
+  ','
+ }
+
+ fn hemisphere_words(&self) -> &'static [(&'static str, f64, AngleDirectionNotation)]
+ {
+  &[("norte", ONE, Latitude), ("sur", -ONE, Latitude), ("este", ONE, Longitude), ("oeste", -ONE, Longitude)]
+ }
+
+ fn unit_words(&self) -> &'static [(&'static str, DmsComponent)]
+ {
+  &[("grados", DmsComponent::Degrees), ("grado", DmsComponent::Degrees)]
+ }
+}
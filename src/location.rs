@@ -7,3 +7,40 @@ pub enum Location
  LonLat(LonLat),
 }
 
+/// The keyword as is, or the `LonLat` with the same flags; eg, `{:#}`
+impl std::fmt::Display for Location
+{
+ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+ {
+  match self
+  {
+   Location::Keyword(keyword) => f.pad(keyword),
+   Location::LonLat(lonlat) => std::fmt::Display::fmt(lonlat, f)
+  }
+ }
+}
+
+impl std::str::FromStr for Location
+{
+ type Err = CivilEngineeringLocationError;
+
+ /// return `Location::LonLat` if the source is a `LonLat` notation, otherwise `Location::Keyword`
+ fn from_str(source: &str) -> Result<Self, Self::Err>
+ {
+  let source = source.trim();
+  if source.is_empty()
+  {
+   Err(ParseError {
+    span: 0..0,
+    component: ParseComponent::Degrees,
+    expected: "a coordinate or a keyword",
+    found: String::new()
+   })?;
+  }
+  Ok(match source.parse::<LonLat>()
+  {
+   Ok(lonlat) => Location::LonLat(lonlat),
+   Err(_) => Location::Keyword(source.into())
+  })
+ }
+}
@@ -1,4 +1,13 @@
-use crate::prelude::*;
+use crate::{
+ dimensions::ddm_formatter,
+ parse::{
+  normalized,
+  parse_lonlat,
+  parse_lonlatalt,
+  strip_geo_uri
+ },
+ prelude::*
+};
 use measurements::{
  Angle,
  Length
@@ -10,6 +19,11 @@ pub trait LonLatGettable
  fn get_lat(&self) -> Angle;
 }
 
+pub trait AltGettable
+{
+ fn get_alt(&self) -> Length;
+}
+
 pub trait LonLatSettable: Default
 {
  fn set_lon(&mut self, lon: Angle);
@@ -38,83 +52,85 @@ impl std::convert::TryFrom<&str> for LonLat
  /// - DMS-like(2): 43°3′43.5″N 141°21′15.8″E
  /// - DEC-like(1): 43.062083,141.354389
  /// - DEC-like(2): 43.062083 141.354389
- /// - GeoURI-like:	geo:43.062083,141.354389
+ /// - GeoURI-like: geo:43.062083,141.354389
  type Error = CivilEngineeringLocationError;
 
  fn try_from(source: &str) -> Result<Self, CivilEngineeringLocationError>
  {
-  let source = source.trim();
-  let source = if source.starts_with("geo:") { &source[4..] } else { &source[..] };
-
-  // "," separated
-  let separated = source.splitn(2, ",").collect::<Vec<_>>();
-  let separated = if separated.len() < 2
-  {
-   // " " separated
-   source.splitn(2, " ").collect::<Vec<_>>()
-  }
-  else
-  {
-   separated
-  };
-
-  let mut lat: Option<Angle> = None;
-  let mut lon: Option<Angle> = None;
-  for (index, source_part) in separated.into_iter().enumerate()
-  {
-   eprintln!("index={} source_part={:?}", index, source_part);
-   let (angle, direction) = Angle::from_dms_str_with_direction(source_part)?;
-   match (index, direction)
-   {
-    (_, AngleDirectionNotation::Latitude) | (0, _) => lat = Some(angle),
-    (_, AngleDirectionNotation::Longitude) | (1, _) => lon = Some(angle),
-    _ => Err(CivilEngineeringLocationError::LonLatFromStrUnknownPattern)?
-   }
-  }
-
-  match (lat, lon)
-  {
-   (Some(lat), Some(lon)) =>
-   {
-    Ok(Self {
-     lat,
-     lon
-    })
-   },
-   _ => Err(CivilEngineeringLocationError::LonLatFromStrUnknownPattern)
-  }
+  Self::parse_with(source, &ParseOptions::default())
  }
 }
 
 impl std::convert::TryFrom<&str> for LonLatAlt
 {
  /// ## Supported notation
- /// - GeoURI-like:	geo:43.062083,141.354389,123.45
- /// - LonLat-convertibles(1):	{`LonLat::try_from`-ables},123.45
- /// - LonLat-convertibles(2):	{`LonLat::try_from`-ables} 123.45
+ /// - GeoURI-like: geo:43.062083,141.354389,123.45
+ /// - LonLat-convertibles(1): {`LonLat::try_from`-ables},123.45
+ /// - LonLat-convertibles(2): {`LonLat::try_from`-ables} 123.45
  type Error = CivilEngineeringLocationError;
 
  fn try_from(source: &str) -> Result<Self, CivilEngineeringLocationError>
  {
-  let source = source.trim();
-  let source = if source.starts_with("geo:") { &source[4..] } else { &source[..] };
+  Self::parse_with(source, &ParseOptions::default())
+ }
+}
+
+impl std::str::FromStr for LonLat
+{
+ type Err = CivilEngineeringLocationError;
+
+ /// Note: The same notations as `LonLat::try_from`.
+ fn from_str(source: &str) -> Result<Self, Self::Err>
+ {
+  <Self as std::convert::TryFrom<&str>>::try_from(source)
+ }
+}
 
-  // "," separated
-  let separated = source.splitn(3, ",").collect::<Vec<_>>();
-  let separated = if separated.len() < 3
+impl std::str::FromStr for LonLatAlt
+{
+ type Err = CivilEngineeringLocationError;
+
+ /// Note: The same notations as `LonLatAlt::try_from`.
+ fn from_str(source: &str) -> Result<Self, Self::Err>
+ {
+  <Self as std::convert::TryFrom<&str>>::try_from(source)
+ }
+}
+
+/// - `{}`: decimal degrees, lat first; eg, `43.062083, 141.354389`
+/// - `{:.4}`: with the decimal places; eg, `43.0621, 141.3544`
+/// - `{:#}`: DMS with N/S and E/W; eg, `43°3’43.5”N 141°21’15.8”E`, and `{:#.2}` for the decimal places of the seconds
+impl std::fmt::Display for LonLat
+{
+ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+ {
+  let formatter = display_formatter(f);
+  match formatter.format(self)
   {
-   // " " separated
-   source.splitn(3, " ").collect::<Vec<_>>()
+   Ok(formatted) => pad(f, &formatted),
+   Err(_) => pad(f, &format!("{}, {}", self.lat.as_degrees(), self.lon.as_degrees()))
   }
-  else
+ }
+}
+
+/// The same as `LonLat` and the altitude [m]; eg, `43.062083, 141.354389, 123.4` or `43°3’43.5”N 141°21’15.8”E 123.4`
+impl std::fmt::Display for LonLatAlt
+{
+ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+ {
+  let formatter = display_formatter(f);
+  let alt = format!("{:.*}", GEO_URI_ALT_DECIMALS, self.alt.as_meters());
+  let alt = match alt.contains('.')
   {
-   separated
+   true => alt.trim_end_matches('0').trim_end_matches('.'),
+   false => &alt[..]
   };
-
-  let alt = Length::from_meters(separated[2].parse::<f64>()?);
-  let lonlat = LonLat::try_from(&format!("{},{}", separated[0], separated[1])[..])?;
-
-  Ok(LonLatAlt::from((lonlat, alt)))
+  let separator = if f.alternate() { " " } else { ", " };
+  match formatter.format(self)
+  {
+   Ok(formatted) => pad(f, &format!("{}{}{}", formatted, separator, alt)),
+   Err(_) => pad(f, &format!("{}, {}, {}", self.lat.as_degrees(), self.lon.as_degrees(), alt))
+  }
  }
 }
 
@@ -127,6 +143,13 @@ impl LonLat
    lat
   }
  }
+
+ /// The same notations as `LonLat::try_from` with the options; eg, `ParseOptions::strict()`
+ pub fn parse_with(source: &str, options: &ParseOptions) -> Result<Self, CivilEngineeringLocationError>
+ {
+  let (source, offset) = strip_geo_uri(source);
+  Ok(normalized(source, offset, options, |source, offset| parse_lonlat(source, offset, options))?)
+ }
 }
 
 impl LonLatAlt
@@ -139,6 +162,13 @@ impl LonLatAlt
    alt
   }
  }
+
+ /// The same notations as `LonLatAlt::try_from` with the options; eg, `ParseOptions::strict()`
+ pub fn parse_with(source: &str, options: &ParseOptions) -> Result<Self, CivilEngineeringLocationError>
+ {
+  let (source, offset) = strip_geo_uri(source);
+  Ok(normalized(source, offset, options, |source, offset| parse_lonlatalt(source, offset, options))?)
+ }
 }
 
 pub trait AsLonLat
@@ -242,6 +272,14 @@ impl LonLatGettable for LonLatAlt
  }
 }
 
+impl AltGettable for LonLatAlt
+{
+ fn get_alt(&self) -> Length
+ {
+  self.alt
+ }
+}
+
 impl LonLatSettable for LonLatAlt
 {
  fn set_lat(&mut self, lat: Angle)
@@ -255,6 +293,7 @@ impl LonLatSettable for LonLatAlt
  }
 }
 
+/// GeoURI(RFC 5870); the longitude is in [-180..180), thus +180° is "-180"; eg, "geo:0,-180"
 pub trait ToStringGeoUri
 {
  fn to_string_geo_uri(&self) -> Result<String, CivilEngineeringLocationError>;
@@ -274,6 +313,7 @@ pub trait ToStringDms
 }
 
 #[cfg(feature = "ja-JP")]
+#[deprecated(note = "Use `CoordinateFormatter::new().locale(&JaJp).hemisphere(HemisphereNotation::SignAlways)`")]
 pub trait ToStringDmsJaJp
 {
  fn to_string_dms_ja_jp(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>;
@@ -301,6 +341,7 @@ pub trait ToStringDmsNwse
 }
 
 #[cfg(feature = "ja-JP")]
+#[deprecated(note = "Use `CoordinateFormatter::new().locale(&JaJp)`")]
 pub trait ToStringDmsNwseJaJp
 {
  fn to_string_dms_nwse_ja_jp(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>;
@@ -314,17 +355,42 @@ pub trait ToStringDmsNwseJaJp
  }
 }
 
+/// Degrees and decimal minutes(DDM); eg, +43°3.725’ +141°21.263’
+///
+/// Note: The other locales are `CoordinateFormatter`; eg,
+/// `.style(CoordinateStyle::DegreesDecimalMinutes).decimals(ANGLE_DDM_DECIMALS).locale(&JaJp)` -> "北緯43度3.725分 東経141度21.263分"
+pub trait ToStringDdm
+{
+ fn to_string_ddm(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>;
+ fn to_string_ddm_comma(&self) -> Result<String, CivilEngineeringLocationError>
+ {
+  self.to_string_ddm(",")
+ }
+ fn to_string_ddm_space(&self) -> Result<String, CivilEngineeringLocationError>
+ {
+  self.to_string_ddm(" ")
+ }
+}
+
+/// Degrees and decimal minutes(DDM) with N/S and E/W; eg, 43°3.725’N 141°21.263’E
+pub trait ToStringDdmNwse
+{
+ fn to_string_ddm_nwse(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>;
+ fn to_string_ddm_nwse_comma(&self) -> Result<String, CivilEngineeringLocationError>
+ {
+  self.to_string_ddm_nwse(",")
+ }
+ fn to_string_ddm_nwse_space(&self) -> Result<String, CivilEngineeringLocationError>
+ {
+  self.to_string_ddm_nwse(" ")
+ }
+}
+
 impl ToStringGeoUri for LonLat
 {
  fn to_string_geo_uri(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let lon = self.get_lon().as_degrees();
-  let lat = self.get_lat().as_degrees();
-  match lon.is_infinite() || lat.is_infinite()
-  {
-   true => Err(CivilEngineeringLocationError::Infinite),
-   false => Ok(format!("geo:{},{}", lat, lon))
-  }
+  Ok(format!("geo:{}", geo_uri_formatter().format(self)?))
  }
 }
 
@@ -332,14 +398,14 @@ impl ToStringGeoUri for LonLatAlt
 {
  fn to_string_geo_uri(&self) -> Result<String, CivilEngineeringLocationError>
  {
-  let lon = self.lon.as_degrees();
-  let lat = self.lat.as_degrees();
   let alt = self.alt.as_meters();
-  match lon.is_infinite() || lat.is_infinite()
+  if alt.is_infinite()
   {
-   true => Err(CivilEngineeringLocationError::Infinite),
-   false => Ok(format!("geo:{},{},{}", lat, lon, alt))
+   Err(CivilEngineeringLocationError::Infinite)?;
   }
+  let alt = format!("{:.*}", GEO_URI_ALT_DECIMALS, alt);
+  let alt = alt.trim_end_matches('0').trim_end_matches('.');
+  Ok(format!("geo:{},{}", geo_uri_formatter().format(self)?, alt))
  }
 }
 
@@ -347,20 +413,21 @@ impl<T: LonLatGettable> ToStringDms for T
 {
  fn to_string_dms(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>
  {
-  let lon = self.get_lon().as_string_dms_180()?;
-  let lat = self.get_lat().as_string_dms_90()?;
-  Ok(format!("{}{}{}", lat, separator, lon))
+  CoordinateFormatter::new().separator(separator).format(self)
  }
 }
 
 #[cfg(feature = "ja-JP")]
+#[allow(deprecated)]
 impl<T: LonLatGettable> ToStringDmsJaJp for T
 {
  fn to_string_dms_ja_jp(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>
  {
-  let lon = self.get_lon().as_string_dms_180_ja_jp()?;
-  let lat = self.get_lat().as_string_dms_90_ja_jp()?;
-  Ok(format!("{}{}{}", lat, separator, lon))
+  CoordinateFormatter::new()
+   .locale(&JaJp)
+   .hemisphere(HemisphereNotation::SignAlways)
+   .separator(separator)
+   .format(self)
  }
 }
 
@@ -368,19 +435,85 @@ impl<T: LonLatGettable> ToStringDmsNwse for T
 {
  fn to_string_dms_nwse(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>
  {
-  let lon = self.get_lon().as_string_dms_ew()?;
-  let lat = self.get_lat().as_string_dms_ns()?;
-  Ok(format!("{}{}{}", lat, separator, lon))
+  CoordinateFormatter::new()
+   .hemisphere(HemisphereNotation::Suffix)
+   .separator(separator)
+   .format(self)
  }
 }
 
 #[cfg(feature = "ja-JP")]
+#[allow(deprecated)]
 impl<T: LonLatGettable> ToStringDmsNwseJaJp for T
 {
  fn to_string_dms_nwse_ja_jp(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>
  {
-  let lon = self.get_lon().as_string_dms_ew_ja_jp()?;
-  let lat = self.get_lat().as_string_dms_ns_ja_jp()?;
-  Ok(format!("{}{}{}", lat, separator, lon))
+  CoordinateFormatter::new()
+   .locale(&JaJp)
+   .separator(separator)
+   .format(self)
  }
 }
+
+impl<T: LonLatGettable> ToStringDdm for T
+{
+ fn to_string_ddm(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>
+ {
+  ddm_formatter().separator(separator).format(self)
+ }
+}
+
+impl<T: LonLatGettable> ToStringDdmNwse for T
+{
+ fn to_string_ddm_nwse(&self, separator: &str) -> Result<String, CivilEngineeringLocationError>
+ {
+  ddm_formatter()
+   .hemisphere(HemisphereNotation::Suffix)
+   .separator(separator)
+   .format(self)
+ }
+}
+
+/// `Formatter::pad` with the width and the alignment only; the precision is the decimal places here.
+fn pad(f: &mut std::fmt::Formatter<'_>, s: &str) -> std::fmt::Result
+{
+ let count = f.width().unwrap_or(0).saturating_sub(s.chars().count());
+ let (before, after) = match f.align()
+ {
+  Some(std::fmt::Alignment::Right) => (count, 0),
+  Some(std::fmt::Alignment::Center) => (count / 2, count - count / 2),
+  _ => (0, count)
+ };
+ let fill = f.fill().to_string();
+ write!(f, "{}{}{}", fill.repeat(before), s, fill.repeat(after))
+}
+
+/// return the formatter of `Display` from the flags; `#` and the precision up to `ANGLE_MAXIMUM_DECIMALS`
+fn display_formatter(f: &std::fmt::Formatter<'_>) -> CoordinateFormatter
+{
+ let precision = f.precision().map(|precision| precision.min(ANGLE_MAXIMUM_DECIMALS));
+ match f.alternate()
+ {
+  true => CoordinateFormatter::new()
+   .decimals(precision.unwrap_or(1))
+   .hemisphere(HemisphereNotation::Suffix),
+  false => CoordinateFormatter::new()
+   .style(CoordinateStyle::DecimalDegrees)
+   .decimals(precision.unwrap_or(GEO_URI_DECIMALS))
+   .trim_zeros(precision.is_none())
+   .symbols(CoordinateSymbols::None)
+   .hemisphere(HemisphereNotation::Sign)
+   .separator(", ")
+ }
+}
+
+fn geo_uri_formatter() -> CoordinateFormatter
+{
+ CoordinateFormatter::new()
+  .style(CoordinateStyle::DecimalDegrees)
+  .decimals(GEO_URI_DECIMALS)
+  .trim_zeros(true)
+  .symbols(CoordinateSymbols::None)
+  .hemisphere(HemisphereNotation::Sign)
+  .separator(",")
+}
@@ -0,0 +1,262 @@
+use crate::prelude::*;
+use measurements::Angle;
+
+/// The decimal places of the degrees in the links of the map services; 1.0e-7 [deg] ≈ 1 [cm]
+pub const MAP_URL_DECIMALS: usize = 7;
+/// The zoom level of the links requiring a zoom level; eg, GSI Maps
+pub const MAP_URL_DEFAULT_ZOOM: f64 = 16.0;
+
+/// The map services of the share links.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum MapService
+{
+ /// "https://www.openstreetmap.org/?mlat=43.062083&mlon=141.354389#map=15/43.062083/141.354389"
+ OpenStreetMap,
+ /// "https://www.google.com/maps/@43.062083,141.354389,15z"
+ GoogleMaps,
+ /// "https://maps.apple.com/?ll=43.062083,141.354389&z=15"
+ AppleMaps,
+ /// "https://www.bing.com/maps?cp=43.062083~141.354389&lvl=15"
+ BingMaps,
+ /// 地理院地図; "https://maps.gsi.go.jp/#15/43.062083/141.354389/"
+ GsiMaps
+}
+
+/// A location in a share link of a map service; the zoom level if the link has it.
+///
+/// - `"https://www.google.com/maps/@43.062083,141.354389,15z".parse::<MapLink>()`
+/// - The markers take precedence over the centers of the views; eg, `mlat` and `mlon` of OpenStreetMap, `q` of Google Maps
+///
+/// Note: The links are parsed offline; the short links (eg, "https://goo.gl/maps/...") must be resolved before.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapLink
+{
+ pub service: MapService,
+ pub lonlat: LonLat,
+ pub zoom: Option<f64>
+}
+
+/// The share links of the map services; the zoom level is omitted if None, except GSI Maps(`MAP_URL_DEFAULT_ZOOM`).
+///
+/// Note: The longitude is in [-180..180) as `ToStringGeoUri`; +180° is "-180".
+pub trait ToStringMapUrl
+{
+ fn to_string_map_url(&self, service: MapService, zoom: Option<f64>) -> Result<String, CivilEngineeringLocationError>;
+}
+
+impl<T: LonLatGettable> ToStringMapUrl for T
+{
+ fn to_string_map_url(&self, service: MapService, zoom: Option<f64>) -> Result<String, CivilEngineeringLocationError>
+ {
+  let formatter = CoordinateFormatter::new()
+   .style(CoordinateStyle::DecimalDegrees)
+   .decimals(MAP_URL_DECIMALS)
+   .trim_zeros(true)
+   .symbols(CoordinateSymbols::None)
+   .hemisphere(HemisphereNotation::Sign);
+  let lat = formatter.format_lat(self.get_lat())?;
+  let lon = formatter.format_lon(self.get_lon())?;
+  if zoom.is_some_and(|zoom| !zoom.is_finite())
+  {
+   Err(CivilEngineeringLocationError::Infinite)?;
+  }
+
+  Ok(match (service, zoom)
+  {
+   (MapService::OpenStreetMap, Some(zoom)) => format!("https://www.openstreetmap.org/?mlat={lat}&mlon={lon}#map={zoom}/{lat}/{lon}"),
+   (MapService::OpenStreetMap, None) => format!("https://www.openstreetmap.org/?mlat={lat}&mlon={lon}"),
+   (MapService::GoogleMaps, Some(zoom)) => format!("https://www.google.com/maps/@{lat},{lon},{zoom}z"),
+   (MapService::GoogleMaps, None) => format!("https://www.google.com/maps?q={lat},{lon}"),
+   (MapService::AppleMaps, Some(zoom)) => format!("https://maps.apple.com/?ll={lat},{lon}&z={zoom}"),
+   (MapService::AppleMaps, None) => format!("https://maps.apple.com/?ll={lat},{lon}"),
+   (MapService::BingMaps, Some(zoom)) => format!("https://www.bing.com/maps?cp={lat}~{lon}&lvl={zoom}"),
+   (MapService::BingMaps, None) => format!("https://www.bing.com/maps?cp={lat}~{lon}"),
+   (MapService::GsiMaps, zoom) => format!("https://maps.gsi.go.jp/#{}/{lat}/{lon}/", zoom.unwrap_or(MAP_URL_DEFAULT_ZOOM))
+  })
+ }
+}
+
+impl std::str::FromStr for MapLink
+{
+ type Err = CivilEngineeringLocationError;
+
+ fn from_str(source: &str) -> Result<Self, Self::Err>
+ {
+  let error = || CivilEngineeringLocationError::MapUrlParseError(source.into());
+  let url = source.trim();
+  let host = url.split("://").nth(1).unwrap_or(url).split(['/', '?', '#']).next().unwrap_or_default().to_ascii_lowercase();
+
+  let (service, found) = if host.ends_with("openstreetmap.org") || host.ends_with("osm.org")
+  {
+   (MapService::OpenStreetMap, parse_openstreetmap(url))
+  }
+  else if host.starts_with("maps.apple.") || (host.ends_with("apple.com") && url.contains("/maps"))
+  {
+   (MapService::AppleMaps, parse_apple_maps(url))
+  }
+  else if host.contains("google.") && (host.starts_with("maps.") || url.contains("/maps"))
+  {
+   (MapService::GoogleMaps, parse_google_maps(url))
+  }
+  else if host.ends_with("bing.com")
+  {
+   (MapService::BingMaps, parse_bing_maps(url))
+  }
+  else if host.ends_with("gsi.go.jp")
+  {
+   (MapService::GsiMaps, parse_gsi_maps(url))
+  }
+  else
+  {
+   return Err(error());
+  };
+
+  let ((lat, lon), zoom) = found.ok_or_else(error)?;
+  if !(lat.abs() <= F90 && lon.abs() <= F180) || zoom.is_some_and(|zoom| !zoom.is_finite())
+  {
+   return Err(error());
+  }
+  Ok(MapLink {
+   service,
+   lonlat: LonLat::new(Angle::from_degrees(lon), Angle::from_degrees(lat)),
+   zoom
+  })
+ }
+}
+
+/// ( ( lat, lon ), zoom ) of a link
+type Found = Option<((f64, f64), Option<f64>)>;
+
+/// "#map=15/43.06/141.35", "?mlat=43.06&mlon=141.35"
+fn parse_openstreetmap(url: &str) -> Found
+{
+ let view = fragment_param(url, "map").and_then(|map| zoom_lat_lon(&map));
+ let marker = query_param(url, "mlat").zip(query_param(url, "mlon")).and_then(|(lat, lon)| Some((lat.parse().ok()?, lon.parse().ok()?)));
+ match (marker, view)
+ {
+  (Some(marker), view) => Some((marker, view.map(|(zoom, _)| zoom))),
+  (None, Some((zoom, center))) => Some((center, Some(zoom))),
+  (None, None) => None
+ }
+}
+
+/// "/@43.06,141.35,15z", "?q=43.06,141.35", "?query=43.06,141.35", "?ll=43.06,141.35", "!3d43.06!4d141.35"
+fn parse_google_maps(url: &str) -> Found
+{
+ let path = url.split(['?', '#']).next().unwrap_or_default();
+ let view = path.split_once("/@").and_then(|(_, view)| {
+  let mut values = view.split('/').next()?.split(',');
+  let center = pair(values.next()?, values.next()?)?;
+  let zoom = values.next().and_then(|zoom| zoom.strip_suffix('z')?.parse().ok());
+  Some((center, zoom))
+ });
+ let place = url.split_once("!3d").and_then(|(_, place)| {
+  let (lat, lon) = place.split_once("!4d")?;
+  pair(lat, lon.split(['!', '?', '/']).next()?)
+ });
+ let marker = ["q", "query", "ll"].iter().find_map(|key| comma_pair(&query_param(url, key)?)).or(place);
+ match (marker, view)
+ {
+  (Some(marker), view) => Some((marker, view.and_then(|(_, zoom)| zoom))),
+  (None, view) => view
+ }
+}
+
+/// "?ll=43.06,141.35&z=15", "?q=43.06,141.35", "?sll=43.06,141.35"
+fn parse_apple_maps(url: &str) -> Found
+{
+ let center = ["ll", "q", "sll", "coordinate"].iter().find_map(|key| comma_pair(&query_param(url, key)?))?;
+ Some((center, query_param(url, "z").and_then(|zoom| zoom.parse().ok())))
+}
+
+/// "?cp=43.06~141.35&lvl=15", "?sp=point.43.06_141.35"
+fn parse_bing_maps(url: &str) -> Found
+{
+ let point = query_param(url, "sp").and_then(|sp| {
+  let (lat, lon) = sp.strip_prefix("point.")?.split_once('_')?;
+  pair(lat, lon.split('_').next()?)
+ });
+ let center = query_param(url, "cp").and_then(|cp| {
+  let (lat, lon) = cp.split_once('~')?;
+  pair(lat, lon)
+ });
+ Some((point.or(center)?, query_param(url, "lvl").and_then(|zoom| zoom.parse().ok())))
+}
+
+/// "#15/43.06/141.35/"
+fn parse_gsi_maps(url: &str) -> Found
+{
+ let (zoom, center) = zoom_lat_lon(url.split_once('#')?.1)?;
+ Some((center, Some(zoom)))
+}
+
+/// "15/43.06/141.35" -> ( zoom, ( lat, lon ) )
+fn zoom_lat_lon(source: &str) -> Option<(f64, (f64, f64))>
+{
+ let mut values = source.split('/');
+ let zoom = values.next()?.parse().ok()?;
+ let center = pair(values.next()?, values.next()?.split('&').next()?)?;
+ Some((zoom, center))
+}
+
+fn pair(lat: &str, lon: &str) -> Option<(f64, f64)>
+{
+ Some((lat.trim().parse().ok()?, lon.trim().parse().ok()?))
+}
+
+/// "43.06,141.35"; "+" and the spaces around the comma are allowed
+fn comma_pair(source: &str) -> Option<(f64, f64)>
+{
+ let (lat, lon) = source.split_once(',')?;
+ pair(lat, lon)
+}
+
+/// The percent-decoded value of a parameter in the query
+fn query_param(url: &str, key: &str) -> Option<String>
+{
+ let query = url.split('#').next()?.split_once('?')?.1;
+ param(query, key)
+}
+
+/// The percent-decoded value of a parameter in the fragment; eg, "map" of "#map=15/43.06/141.35"
+fn fragment_param(url: &str, key: &str) -> Option<String>
+{
+ param(url.split_once('#')?.1, key)
+}
+
+fn param(params: &str, key: &str) -> Option<String>
+{
+ params
+  .split('&')
+  .find_map(|param| param.strip_prefix(key)?.strip_prefix('='))
+  .map(percent_decode)
+}
+
+/// "43.06%2C141.35" -> "43.06,141.35"; "+" is a space
+fn percent_decode(source: &str) -> String
+{
+ let mut decoded = Vec::with_capacity(source.len());
+ let mut bytes = source.bytes();
+ while let Some(b) = bytes.next()
+ {
+  match b
+  {
+   b'%' =>
+   {
+    let hex = bytes.clone().take(2).collect::<Vec<_>>();
+    match std::str::from_utf8(&hex).ok().filter(|hex| hex.len() == 2 && hex.bytes().all(|c| c.is_ascii_hexdigit()))
+    {
+     Some(hex) =>
+     {
+      decoded.push(u8::from_str_radix(hex, 16).unwrap_or(b));
+      bytes.nth(1);
+     },
+     _ => decoded.push(b)
+    }
+   },
+   b'+' => decoded.push(b' '),
+   _ => decoded.push(b)
+  }
+ }
+ String::from_utf8_lossy(&decoded).into()
+}
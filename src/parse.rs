@@ -0,0 +1,1315 @@
+use crate::prelude::*;
+use measurements::{
+ Angle,
+ Length
+};
+use std::ops::Range;
+
+/// How strictly the parsers accept a notation.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ParseMode
+{
+ /// Any text without digits before and after the notation, and any values; eg, `"lat: 43°75′ (approx)"`
+ Lenient,
+ /// No extra text except spaces, the minutes and the seconds < 60, no seconds after the decimal minutes,
+ /// the latitude within ±90° and the longitude within ±180°
+ Strict
+}
+
+/// The order of the axes without the hemispheres.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ParseAxisOrder
+{
+ /// Latitude first; eg, ISO 6709 and GeoURI
+ LatLon,
+ /// Longitude first; eg, GeoJSON, WKT and many CSVs
+ LonLat,
+ /// By the ranges(|latitude| <= 90°), then by `ParseOptions::reference`; otherwise `ParseComponent::AxisOrder`
+ Auto
+}
+
+/// The locales of the default `ParseOptions`
+#[cfg(not(feature = "ja-JP"))]
+pub const DEFAULT_PARSE_LOCALES: &[&dyn Locale] = &[&En];
+/// The locales of the default `ParseOptions`
+#[cfg(feature = "ja-JP")]
+pub const DEFAULT_PARSE_LOCALES: &[&dyn Locale] = &[&En, &JaJp];
+
+/// The options of the parsers; eg, `LonLat::parse_with`.
+///
+/// The default is `ParseMode::Lenient`, `DEFAULT_PARSE_LOCALES` and `ParseAxisOrder::LatLon`;
+/// the same as `LonLat::try_from` and `Angle::from_dms_str`.
+///
+/// Note: The hemispheres decide the axes in any `ParseAxisOrder`; eg, "141E 43N".
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions
+{
+ mode: ParseMode,
+ locales: &'static [&'static dyn Locale],
+ order: ParseAxisOrder,
+ reference: Option<LonLatBounds>
+}
+
+impl Default for ParseOptions
+{
+ fn default() -> Self
+ {
+  Self {
+   mode: ParseMode::Lenient,
+   locales: DEFAULT_PARSE_LOCALES,
+   order: ParseAxisOrder::LatLon,
+   reference: None
+  }
+ }
+}
+
+impl ParseOptions
+{
+ pub fn new() -> Self
+ {
+  Self::default()
+ }
+
+ pub fn strict() -> Self
+ {
+  Self::default().mode(ParseMode::Strict)
+ }
+
+ pub fn lenient() -> Self
+ {
+  Self::default().mode(ParseMode::Lenient)
+ }
+
+ pub fn mode(mut self, mode: ParseMode) -> Self
+ {
+  self.mode = mode;
+  self
+ }
+
+ /// The hemispheres, the units and the decimal separators of the locales; eg, `&[&En, &De]`
+ ///
+ /// Note: If any of the locales uses "," for the decimals(eg, `Fr`), ";" separates the latitude and the longitude.
+ ///
+ /// Note: The earlier locales take precedence for the same words; eg, "O" is the west in `Fr` and `Es` but the east in `De`,
+ ///       thus "141°O" is 141°W with `&[&Fr, &De]` and 141°E with `&[&De, &Fr]`.
+ pub fn locales(mut self, locales: &'static [&'static dyn Locale]) -> Self
+ {
+  self.locales = locales;
+  self
+ }
+
+ pub fn order(mut self, order: ParseAxisOrder) -> Self
+ {
+  self.order = order;
+  self
+ }
+
+ /// The region where the coordinates are expected; for `ParseAxisOrder::Auto` if the both may be latitudes
+ pub fn reference(mut self, reference: LonLatBounds) -> Self
+ {
+  self.reference = Some(reference);
+  self
+ }
+
+ fn is_strict(&self) -> bool
+ {
+  self.mode == ParseMode::Strict
+ }
+
+ fn decimal_comma(&self) -> bool
+ {
+  self.locales.iter().any(|locale| locale.decimal_separator() == ',')
+ }
+
+ /// The separator between the latitude and the longitude, and before the altitude
+ fn separator(&self) -> char
+ {
+  if self.decimal_comma() { ';' } else { ',' }
+ }
+}
+
+/// An angle parsed from a part of a source.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct ParsedAngle
+{
+ pub angle: Angle,
+ pub direction: AngleDirectionNotation,
+ /// The byte span of the angle in the source; from the sign or the first number to the hemisphere
+ pub start: usize,
+ pub end: usize
+}
+
+/// The state of the components; the units must appear in this order.
+#[derive(Debug, Eq, PartialEq, PartialOrd, Copy, Clone)]
+enum Unit
+{
+ None,
+ Degrees,
+ Minutes,
+ Seconds
+}
+
+struct Cursor<'a>
+{
+ source: &'a str,
+ position: usize,
+ /// The byte offset of the source in the original input; for the spans of the errors
+ offset: usize,
+ /// "," is also the decimal separator
+ decimal_comma: bool
+}
+
+impl<'a> Cursor<'a>
+{
+ fn new(source: &'a str, offset: usize) -> Self
+ {
+  Self {
+   source,
+   position: 0,
+   offset,
+   decimal_comma: false
+  }
+ }
+
+ fn with_options(mut self, options: &ParseOptions) -> Self
+ {
+  self.decimal_comma = options.decimal_comma();
+  self
+ }
+
+ fn rest(&self) -> &'a str
+ {
+  &self.source[self.position..]
+ }
+
+ fn peek(&self) -> Option<char>
+ {
+  self.rest().chars().next()
+ }
+
+ fn bump(&mut self) -> Option<char>
+ {
+  let c = self.peek()?;
+  self.position += c.len_utf8();
+  Some(c)
+ }
+
+ fn skip_spaces(&mut self)
+ {
+  while self.peek().is_some_and(char::is_whitespace)
+  {
+   self.bump();
+  }
+ }
+
+ fn starts_number(&self) -> bool
+ {
+  let mut chars = self.rest().chars();
+  match chars.next()
+  {
+   Some(c) if c.is_ascii_digit() => true,
+   Some('.') => chars.next().is_some_and(|c| c.is_ascii_digit()),
+   _ => false
+  }
+ }
+
+ fn skip_digits(&mut self)
+ {
+  while self.peek().is_some_and(|c| c.is_ascii_digit())
+  {
+   self.bump();
+  }
+ }
+
+ /// return the byte span of a number; digits, the decimal places and the exponent
+ fn number(&mut self) -> Option<(usize, usize)>
+ {
+  if !self.starts_number()
+  {
+   return None;
+  }
+  let start = self.position;
+  self.skip_digits();
+  if self.starts_with_fraction() || (self.decimal_comma && self.starts_with_decimal_comma())
+  {
+   self.bump();
+   self.skip_digits();
+  }
+  let before_exponent = self.position;
+  if let Some('e') | Some('E') = self.bump()
+  {
+   if let Some('+') | Some('-') = self.peek()
+   {
+    self.bump();
+   }
+   if self.peek().is_some_and(|c| c.is_ascii_digit())
+   {
+    self.skip_digits();
+   }
+   else
+   {
+    self.position = before_exponent;
+   }
+  }
+  else
+  {
+   self.position = before_exponent;
+  }
+  Some((start, self.position))
+ }
+
+ fn starts_with_fraction(&self) -> bool
+ {
+  let mut chars = self.rest().chars();
+  chars.next() == Some('.') && chars.next().is_some_and(|c| c.is_ascii_digit())
+ }
+
+ fn starts_with_decimal_comma(&self) -> bool
+ {
+  let mut chars = self.rest().chars();
+  chars.next() == Some(',') && chars.next().is_some_and(|c| c.is_ascii_digit())
+ }
+
+ /// return the byte length of the next character; 0 at the end
+ fn next_len(&self) -> usize
+ {
+  self.peek().map(char::len_utf8).unwrap_or(0)
+ }
+
+ fn error(&self, start: usize, end: usize, component: ParseComponent, expected: &'static str) -> ParseError
+ {
+  ParseError {
+   span: self.offset + start..self.offset + end,
+   component,
+   expected,
+   found: self.source[start..end].into()
+  }
+ }
+}
+
+/// return true if the source starts with the word and the word ends there; eg, "n" of "n 43" but not of "nov".
+///
+/// Note: The words not ending with an ASCII letter end anywhere; eg, "北緯" of "北緯43度"
+fn starts_with_word(source: &str, word: &str) -> bool
+{
+ source.get(..word.len()).is_some_and(|head| head.eq_ignore_ascii_case(word))
+  && !(word.ends_with(|c: char| c.is_ascii_alphabetic()) && source[word.len()..].starts_with(|c: char| c.is_alphabetic()))
+}
+
+/// return true if the source starts with the unit word and the word ends there or a hemisphere follows; eg, "sec" of "secN"
+fn starts_with_unit_word(source: &str, word: &str, options: &ParseOptions) -> bool
+{
+ starts_with_word(source, word)
+  || (source.get(..word.len()).is_some_and(|head| head.eq_ignore_ascii_case(word))
+   && hemisphere_at(&source[word.len()..], None, options).is_some())
+}
+
+/// return ( sign, direction, byte length ) of the hemisphere at the head of the source; the words of the locales.
+///
+/// previous := the character before the source; the words starting with an ASCII letter must start a word
+fn hemisphere_at(source: &str, previous: Option<char>, options: &ParseOptions) -> Option<(f64, AngleDirectionNotation, usize)>
+{
+ use AngleDirectionNotation::*;
+ let is_start = |word: &str| !(word.starts_with(|c: char| c.is_ascii_alphabetic()) && previous.is_some_and(char::is_alphabetic));
+ options.locales.iter().find_map(|locale| {
+  let (north, south, east, west) = locale.hemispheres();
+  let letters = [(north, ONE, Latitude), (south, -ONE, Latitude), (east, ONE, Longitude), (west, -ONE, Longitude)];
+  locale
+   .hemisphere_words()
+   .iter()
+   .chain(letters.iter())
+   .find(|(word, _, _)| is_start(word) && starts_with_word(source, word))
+   .map(|&(word, sign, direction)| (sign, direction, word.len()))
+ })
+}
+
+/// return ( unit, byte length ) of the unit just after a number; the spaces are allowed before the unit words.
+///
+/// The single letters are lowercase only and need the larger units before; eg, "43d 3m 43.5s" but "43.5s" is 43.5°S.
+fn unit_at(source: &str, last: Unit, options: &ParseOptions) -> (Unit, usize)
+{
+ let symbol = match source.chars().next()
+ {
+  Some('°') => Unit::Degrees,
+  Some('′') | Some('’') | Some('\'') => Unit::Minutes,
+  Some('″') | Some('”') | Some('"') => Unit::Seconds,
+  _ => Unit::None
+ };
+ if symbol != Unit::None
+ {
+  return (symbol, source.chars().next().map(char::len_utf8).unwrap_or(0));
+ }
+
+ // The symbols of the locales; eg, "度"
+ let symbol = options.locales.iter().find_map(|locale| {
+  let (degrees, minutes, seconds) = locale.units();
+  [(degrees, Unit::Degrees), (minutes, Unit::Minutes), (seconds, Unit::Seconds)]
+   .iter()
+   .find(|(symbol, _)| !symbol.is_empty() && source.starts_with(symbol))
+   .map(|&(symbol, unit)| (unit, symbol.len()))
+ });
+ if let Some(symbol) = symbol
+ {
+  return symbol;
+ }
+
+ let trimmed = source.trim_start();
+ let spaces = source.len() - trimmed.len();
+ let word = options
+  .locales
+  .iter()
+  .flat_map(|locale| locale.unit_words().iter())
+  .find(|(word, _)| starts_with_unit_word(trimmed, word, options));
+ if let Some(&(word, unit)) = word
+ {
+  let unit = match unit
+  {
+   DmsComponent::Degrees => Unit::Degrees,
+   DmsComponent::Minutes => Unit::Minutes,
+   DmsComponent::Seconds => Unit::Seconds
+  };
+  return (unit, spaces + word.len());
+ }
+ let letter = match last
+ {
+  Unit::None if starts_with_unit_word(trimmed, "d", options) && trimmed.starts_with('d') => Unit::Degrees,
+  Unit::Degrees if starts_with_unit_word(trimmed, "m", options) && trimmed.starts_with('m') => Unit::Minutes,
+  Unit::Degrees | Unit::Minutes if starts_with_unit_word(trimmed, "s", options) && trimmed.starts_with('s') => Unit::Seconds,
+  _ => Unit::None
+ };
+ match letter
+ {
+  Unit::None => (Unit::None, 0),
+  _ => (letter, spaces + 1)
+ }
+}
+
+/// Parse an angle from the head of the source; the rest after the angle is not checked.
+///
+/// - The hemisphere may be before or after the value; "N 43°3′43.5″", "S43.5", "43.5 North" and the lowercases.
+/// - The units may be the words; "43 deg 3 min 43.5 sec W", "43d 3m 43.5s W".
+///
+/// Note: Any characters except digits and signs before the angle are skipped; only the spaces in `ParseMode::Strict`.
+pub(crate) fn parse_angle(source: &str, offset: usize, options: &ParseOptions) -> Result<ParsedAngle, ParseError>
+{
+ let mut cursor = Cursor::new(source, offset).with_options(options);
+ let mut prefix: Option<(f64, AngleDirectionNotation)> = None;
+ let mut prefix_start = None;
+
+ // head
+ let mut previous: Option<char> = None;
+ while let Some(c) = cursor.peek()
+ {
+  if c.is_ascii_digit() || c == '+' || c == '-' || cursor.starts_with_fraction()
+  {
+   break;
+  }
+  // A hemisphere just before the value; eg, "N 43°", "S43.5", "北緯43度"
+  if let Some((sign, direction, length)) = hemisphere_at(cursor.rest(), previous, options)
+  {
+   let mut lookahead = Cursor::new(source, offset);
+   lookahead.position = cursor.position + length;
+   lookahead.skip_spaces();
+   if lookahead.starts_number()
+   {
+    prefix = Some((sign, direction));
+    prefix_start = Some(cursor.position);
+    cursor.position = lookahead.position;
+    break;
+   }
+  }
+  if options.is_strict() && !c.is_whitespace()
+  {
+   return Err(cursor.error(cursor.position, cursor.position + c.len_utf8(), ParseComponent::Degrees, "no text before the angle"));
+  }
+  previous = cursor.bump();
+ }
+
+ let start = prefix_start.unwrap_or(cursor.position);
+ let sign_start = cursor.position;
+ let sign = match cursor.peek()
+ {
+  Some('+') =>
+  {
+   cursor.bump();
+   Some(ONE)
+  },
+  Some('-') =>
+  {
+   cursor.bump();
+   Some(-ONE)
+  },
+  _ => None
+ };
+
+ let mut values = [ZERO; 3];
+ let mut degrees_only: Option<f64> = None;
+ let mut last = Unit::None;
+ let mut decimal_minutes = false;
+ loop
+ {
+  let component = match last
+  {
+   Unit::None => ParseComponent::Degrees,
+   Unit::Degrees => ParseComponent::Minutes,
+   _ => ParseComponent::Seconds
+  };
+  let (number_start, number_end) = match cursor.number()
+  {
+   Some(span) => span,
+   None if sign.is_some() && last == Unit::None =>
+   {
+    return Err(cursor.error(sign_start, cursor.position, ParseComponent::Sign, "a number after the sign"));
+   },
+   None => return Err(cursor.error(cursor.position, cursor.position + cursor.next_len(), component, "a number"))
+  };
+  let text = &source[number_start..number_end];
+  let value = parse_number(text).ok_or_else(|| cursor.error(number_start, number_end, component, "a number"))?;
+
+  let unit_start = cursor.position;
+  let (unit, unit_length) = unit_at(cursor.rest(), last, options);
+
+  match (last, unit)
+  {
+   (Unit::None, Unit::None) =>
+   {
+    degrees_only = Some(value);
+    break;
+   },
+   (_, Unit::None) =>
+   {
+    let expected = if last == Unit::Degrees { "′ after the minutes" } else { "″ after the seconds" };
+    return Err(cursor.error(number_start, number_end, component, expected));
+   },
+   _ if unit <= last =>
+   {
+    let expected = if last == Unit::Degrees { "′ or ″ after the degrees" } else { "″ after the minutes" };
+    let component = match unit
+    {
+     Unit::Degrees => ParseComponent::Degrees,
+     Unit::Minutes => ParseComponent::Minutes,
+     _ => ParseComponent::Seconds
+    };
+    return Err(cursor.error(unit_start, unit_start + unit_length, component, expected));
+   },
+   _ => ()
+  }
+
+  if options.is_strict() && last != Unit::None && value >= F60
+  {
+   let expected = if unit == Unit::Minutes { "the minutes less than 60" } else { "the seconds less than 60" };
+   return Err(cursor.error(number_start, number_end, component, expected));
+  }
+
+  cursor.position += unit_length;
+  match unit
+  {
+   Unit::Degrees => values[0] = value,
+   Unit::Minutes =>
+   {
+    values[1] = value;
+    decimal_minutes = text.contains(['.', 'e', 'E']) || (options.decimal_comma() && text.contains(','));
+   },
+   _ =>
+   {
+    if decimal_minutes && options.is_strict()
+    {
+     return Err(cursor.error(number_start, cursor.position, ParseComponent::Seconds, "no seconds after the decimal minutes"));
+    }
+    values[2] = value;
+   }
+  }
+  last = unit;
+  if last == Unit::Seconds
+  {
+   break;
+  }
+
+  // The next component; or the next angle after the spaces
+  let before_spaces = cursor.position;
+  cursor.skip_spaces();
+  if !cursor.starts_number() || (cursor.position > before_spaces && !continues_with_smaller_unit(&cursor, last, options))
+  {
+   cursor.position = before_spaces;
+   break;
+  }
+ }
+
+ // hemisphere; after the value if not before
+ let mut suffix = None;
+ if prefix.is_none()
+ {
+  let before_hemisphere = cursor.position;
+  cursor.skip_spaces();
+  match hemisphere_at(cursor.rest(), None, options)
+  {
+   Some((sign, direction, length)) =>
+   {
+    suffix = Some((sign, direction));
+    cursor.position += length;
+   },
+   None => cursor.position = before_hemisphere
+  }
+ }
+
+ // An explicit sign takes precedence over the hemisphere.
+ let (sign, direction) = match (sign, suffix.or(prefix))
+ {
+  (Some(sign), _) => (sign, AngleDirectionNotation::None),
+  (None, Some(hemisphere)) => hemisphere,
+  (None, None) => (ONE, AngleDirectionNotation::None)
+ };
+
+ let degrees = match degrees_only
+ {
+  Some(degrees) => degrees,
+  None => values[0] + values[1] / F60 + values[2] / F60 / F60
+ };
+
+ Ok(ParsedAngle {
+  angle: Angle::from_degrees(sign * degrees),
+  direction,
+  start: offset + start,
+  end: offset + cursor.position
+ })
+}
+
+/// return true if the number at the cursor has a unit smaller than the last; the same angle continues
+fn continues_with_smaller_unit(cursor: &Cursor, last: Unit, options: &ParseOptions) -> bool
+{
+ let mut lookahead = Cursor::new(cursor.source, cursor.offset).with_options(options);
+ lookahead.position = cursor.position;
+ lookahead.number();
+ unit_at(lookahead.rest(), last, options).0 > last
+}
+
+/// The bytes of the numbers with the decimal comma parsed without any allocations
+const NUMBER_BUFFER_LENGTH: usize = 64;
+
+/// Parse a number of `Cursor::number`; "," as the decimal separator is replaced without any allocations.
+///
+/// Note: The numbers longer than `NUMBER_BUFFER_LENGTH` bytes with "," are replaced in a `String`; eg, too many decimals.
+fn parse_number(text: &str) -> Option<f64>
+{
+ if !text.contains(',')
+ {
+  return text.parse().ok();
+ }
+ let mut buffer = [0u8; NUMBER_BUFFER_LENGTH];
+ let buffer = match buffer.get_mut(..text.len())
+ {
+  Some(buffer) => buffer,
+  None => return text.replace(',', ".").parse().ok()
+ };
+ for (b, c) in buffer.iter_mut().zip(text.bytes())
+ {
+  *b = if c == b',' { b'.' } else { c };
+ }
+ std::str::from_utf8(buffer).ok()?.parse().ok()
+}
+
+/// Parse an angle from the whole source; the rest after the angle must not have any digits.
+pub(crate) fn parse_angle_to_end(source: &str, offset: usize, options: &ParseOptions) -> Result<ParsedAngle, ParseError>
+{
+ let parsed = parse_angle(source, offset, options)?;
+ check_end(source, offset, parsed.end - offset, options)?;
+ check_range(&parsed, parsed.direction, source, offset, options)?;
+ Ok(parsed)
+}
+
+/// Parse a `LonLat`; "lat,lon", "lat lon" or the both with the hemispheres in any order.
+pub(crate) fn parse_lonlat(source: &str, offset: usize, options: &ParseOptions) -> Result<LonLat, ParseError>
+{
+ let (first, second) = parse_pair(source, offset, options)?;
+ check_end(source, offset, second.end - offset, options)?;
+ assign(&first, &second, source, offset, options)
+}
+
+/// Parse a `LonLatAlt`; "lat,lon,alt" or "lat lon alt"; the altitude is in meters.
+pub(crate) fn parse_lonlatalt(source: &str, offset: usize, options: &ParseOptions) -> Result<LonLatAlt, ParseError>
+{
+ let (first, second) = parse_pair(source, offset, options)?;
+ let lonlat = assign(&first, &second, source, offset, options)?;
+
+ let mut cursor = Cursor::new(source, offset);
+ cursor.position = second.end - offset;
+ let before_separator = cursor.position;
+ cursor.skip_spaces();
+ let has_comma = cursor.peek() == Some(options.separator());
+ if has_comma
+ {
+  cursor.bump();
+  cursor.skip_spaces();
+ }
+ if cursor.position == before_separator || cursor.peek().is_none()
+ {
+  let expected = if has_comma { "an altitude [m]" } else { "a comma or a space before the altitude" };
+  let component = if has_comma { ParseComponent::Altitude } else { ParseComponent::Separator };
+  return Err(cursor.error(cursor.position, cursor.position + cursor.next_len(), component, expected));
+ }
+
+ let alt = source[cursor.position..].trim_end();
+ let alt_start = cursor.position;
+ let alt = match options.decimal_comma()
+ {
+  true => parse_number(alt),
+  false => alt.parse::<f64>().ok()
+ }
+ .ok_or_else(|| cursor.error(alt_start, alt_start + alt.len(), ParseComponent::Altitude, "an altitude [m]"))?;
+ Ok(LonLatAlt::from((lonlat, Length::from_meters(alt))))
+}
+
+/// return ( the trimmed source without "geo:", the offset of it )
+pub(crate) fn strip_geo_uri(source: &str) -> (&str, usize)
+{
+ let trimmed = source.trim();
+ let offset = source.len() - source.trim_start().len();
+ match trimmed.strip_prefix("geo:")
+ {
+  Some(stripped) => (stripped, offset + 4),
+  None => (trimmed, offset)
+ }
+}
+
+/// Parse the two angles separated by a comma or spaces.
+fn parse_pair(source: &str, offset: usize, options: &ParseOptions) -> Result<(ParsedAngle, ParsedAngle), ParseError>
+{
+ let first = parse_angle(source, offset, options)?;
+
+ let mut cursor = Cursor::new(source, offset);
+ cursor.position = first.end - offset;
+ let before_separator = cursor.position;
+ match source[before_separator..].find(options.separator()).map(|comma| before_separator + comma)
+ {
+  // "," (or ";") separated; any characters except digits are allowed around the comma in `ParseMode::Lenient`
+  Some(comma) =>
+  {
+   check_end(&source[..comma], offset, before_separator, options)?;
+   cursor.position = comma + 1;
+  },
+  // " " separated
+  None =>
+  {
+   cursor.skip_spaces();
+   if cursor.position == before_separator || cursor.peek().is_none()
+   {
+    return Err(cursor.error(
+     cursor.position,
+     cursor.position + cursor.next_len(),
+     ParseComponent::Separator,
+     "a comma or a space between the latitude and the longitude"
+    ));
+   }
+  },
+ }
+
+ let second = parse_angle(&source[cursor.position..], offset + cursor.position, options)?;
+ Ok((first, second))
+}
+
+/// return the `LonLat` from the two angles; by the hemispheres, otherwise by `ParseAxisOrder`
+fn assign(first: &ParsedAngle, second: &ParsedAngle, source: &str, offset: usize, options: &ParseOptions) -> Result<LonLat, ParseError>
+{
+ let error = |parsed: &ParsedAngle, expected: &'static str| {
+  let cursor = Cursor::new(source, offset);
+  cursor.error(parsed.start - offset, parsed.end - offset, ParseComponent::Hemisphere, expected)
+ };
+
+ use AngleDirectionNotation::*;
+ let (lon, lat) = match (first.direction, second.direction)
+ {
+  (Latitude, Latitude) => return Err(error(second, "E or W; the both are latitudes")),
+  (Longitude, Longitude) => return Err(error(second, "N or S; the both are longitudes")),
+  (Longitude, _) | (None, Latitude) => (first, second),
+  (Latitude, _) | (None, Longitude) => (second, first),
+  (None, None) =>
+  {
+   let (lon, lat) = order(first, second, source, offset, options)?;
+   if options.is_strict() && lat.angle.as_degrees().abs() > F90 && lon.angle.as_degrees().abs() <= F90
+   {
+    let cursor = Cursor::new(source, offset);
+    let expected = "a latitude within ±90°; the axes may be swapped, see `ParseAxisOrder`";
+    return Err(cursor.error(lat.start - offset, lat.end - offset, ParseComponent::Degrees, expected));
+   }
+   (lon, lat)
+  },
+ };
+ check_range(lon, Longitude, source, offset, options)?;
+ check_range(lat, Latitude, source, offset, options)?;
+ Ok(LonLat::new(lon.angle, lat.angle))
+}
+
+/// return ( lon, lat ) of the angles without the hemispheres
+fn order<'a>(
+ first: &'a ParsedAngle,
+ second: &'a ParsedAngle,
+ source: &str,
+ offset: usize,
+ options: &ParseOptions
+) -> Result<(&'a ParsedAngle, &'a ParsedAngle), ParseError>
+{
+ match options.order
+ {
+  ParseAxisOrder::LatLon => return Ok((second, first)),
+  ParseAxisOrder::LonLat => return Ok((first, second)),
+  ParseAxisOrder::Auto => ()
+ }
+
+ // The same values in the both orders
+ if first.angle == second.angle
+ {
+  return Ok((second, first));
+ }
+
+ let may_be_latitude = |parsed: &ParsedAngle| parsed.angle.as_degrees().abs() <= F90;
+ match (may_be_latitude(first), may_be_latitude(second))
+ {
+  (true, false) => return Ok((second, first)),
+  (false, true) => return Ok((first, second)),
+  _ => ()
+ }
+
+ if let Some(reference) = &options.reference
+ {
+  let lat_lon = reference.contains(&LonLat::new(second.angle, first.angle));
+  let lon_lat = reference.contains(&LonLat::new(first.angle, second.angle));
+  match (lat_lon, lon_lat)
+  {
+   (true, false) => return Ok((second, first)),
+   (false, true) => return Ok((first, second)),
+   _ => ()
+  }
+ }
+
+ let cursor = Cursor::new(source, offset);
+ let expected = "N, S, E or W, or a reference region to decide the axis order";
+ Err(cursor.error(first.start - offset, second.end - offset, ParseComponent::AxisOrder, expected))
+}
+
+/// Find the coordinates in a free text; the pairs of the angles at the starts of the words in `ParseMode::Strict`.
+///
+/// return ( the coordinate, the byte span in the source ) of each; the span includes "geo:" before the pair.
+pub(crate) fn scan_lonlats(source: &str, options: &ParseOptions) -> Vec<(LonLat, Range<usize>)>
+{
+ let normalized = match options.locales.iter().any(|locale| locale.normalizes_cjk())
+ {
+  true => normalize_cjk(source),
+  false => None
+ };
+ match normalized
+ {
+  Some((normalized, map)) =>
+  {
+   scan(&normalized, options)
+    .into_iter()
+    .map(|(lonlat, span)| (lonlat, map[span.start]..map[span.end]))
+    .collect()
+  },
+  None => scan(source, options)
+ }
+}
+
+fn scan(source: &str, options: &ParseOptions) -> Vec<(LonLat, Range<usize>)>
+{
+ let options = options.clone().mode(ParseMode::Strict);
+ // Not in the middle of a word, a number or an angle; eg, "v1.2, 3.4", "A4 5" or "75′N" of "43°75′N"
+ let is_unit = |p: char| {
+  matches!(p, '°' | '′' | '’' | '\'' | '″' | '”' | '"')
+   || options.locales.iter().any(|locale| {
+    let (d, m, s) = locale.units();
+    [d, m, s].iter().any(|unit| unit.ends_with(p))
+   })
+ };
+ let can_start = |previous: Option<char>, c: char| {
+  !c.is_whitespace() && !previous.is_some_and(|p| p.is_ascii_alphanumeric() || p == '.' || p == '_' || is_unit(p))
+ };
+
+ let mut found = vec![];
+ let mut position = 0;
+ let mut previous = None;
+ while let Some(c) = source[position..].chars().next()
+ {
+  if can_start(previous, c)
+  {
+   if let Some((lonlat, start, end)) = scan_pair(source, position, &options)
+   {
+    let start = if source[..start].ends_with("geo:") { start - 4 } else { start };
+    found.push((lonlat, start..end));
+    position = end;
+    previous = source[..end].chars().next_back();
+    continue;
+   }
+  }
+  previous = Some(c);
+  position += c.len_utf8();
+ }
+ found
+}
+
+/// return ( the coordinate, the start, the end ) of the pair at the position; None if not a coordinate.
+///
+/// Note: The pairs without any units or hemispheres must have the decimal places; eg, "43.06, 141.35" but not "10 15".
+fn scan_pair(source: &str, position: usize, options: &ParseOptions) -> Option<(LonLat, usize, usize)>
+{
+ let first = parse_angle(&source[position..], position, options).ok()?;
+
+ let mut cursor = Cursor::new(source, 0);
+ cursor.position = first.end;
+ cursor.skip_spaces();
+ if cursor.peek() == Some(options.separator())
+ {
+  cursor.bump();
+  cursor.skip_spaces();
+ }
+ else if cursor.position == first.end
+ {
+  return None;
+ }
+ let second = parse_angle(&source[cursor.position..], cursor.position, options).ok()?;
+ if source[second.end..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+ {
+  return None;
+ }
+
+ let text = |parsed: &ParsedAngle| &source[parsed.start..parsed.end];
+ let is_plain = |parsed: &ParsedAngle| text(parsed).chars().all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | ',' | 'e' | 'E'));
+ let has_decimals = |parsed: &ParsedAngle| text(parsed).contains('.') || (options.decimal_comma() && text(parsed).contains(','));
+ if [&first, &second].iter().any(|parsed| is_plain(parsed) && !has_decimals(parsed))
+ {
+  return None;
+ }
+
+ let lonlat = assign(&first, &second, source, 0, options).ok()?;
+ Some((lonlat, first.start, second.end))
+}
+
+/// The latitude must be within ±90° and the longitude within ±180° in `ParseMode::Strict`.
+fn check_range(
+ parsed: &ParsedAngle,
+ direction: AngleDirectionNotation,
+ source: &str,
+ offset: usize,
+ options: &ParseOptions
+) -> Result<(), ParseError>
+{
+ let (limit, expected) = match direction
+ {
+  AngleDirectionNotation::Latitude => (F90, "a latitude within ±90°"),
+  AngleDirectionNotation::Longitude => (F180, "a longitude within ±180°"),
+  AngleDirectionNotation::None => return Ok(())
+ };
+ if options.is_strict() && parsed.angle.as_degrees().abs() > limit
+ {
+  let cursor = Cursor::new(source, offset);
+  return Err(cursor.error(parsed.start - offset, parsed.end - offset, ParseComponent::Degrees, expected));
+ }
+ Ok(())
+}
+
+/// The rest from the position must not have any digits; any text except spaces in `ParseMode::Strict`.
+fn check_end(source: &str, offset: usize, position: usize, options: &ParseOptions) -> Result<(), ParseError>
+{
+ let mut cursor = Cursor::new(source, offset);
+ cursor.position = position;
+ while let Some(c) = cursor.peek()
+ {
+  if options.is_strict() && !c.is_whitespace() && !c.is_ascii_digit()
+  {
+   let start = cursor.position;
+   return Err(cursor.error(start, source.trim_end().len().max(start + c.len_utf8()), ParseComponent::End, "no text after the angle"));
+  }
+  if c.is_ascii_digit()
+  {
+   let start = cursor.position;
+   cursor.skip_digits();
+   return Err(cursor.error(start, cursor.position, ParseComponent::End, "the end of the angle"));
+  }
+  cursor.bump();
+ }
+ Ok(())
+}
+
+/// The words of the other angular units; the longer words first
+const ANGULAR_UNIT_WORDS: &[(&str, AngularUnit)] = &[
+ ("gons", AngularUnit::Gradians),
+ ("gon", AngularUnit::Gradians),
+ ("grads", AngularUnit::Gradians),
+ ("grad", AngularUnit::Gradians),
+ ("ᵍ", AngularUnit::Gradians),
+ ("mils", AngularUnit::Mils),
+ ("mil", AngularUnit::Mils),
+ ("turns", AngularUnit::Turns),
+ ("turn", AngularUnit::Turns),
+ ("rev", AngularUnit::Turns),
+ ("tr", AngularUnit::Turns),
+ ("hours", AngularUnit::Hours),
+ ("hour", AngularUnit::Hours),
+ ("h", AngularUnit::Hours),
+ ("ʰ", AngularUnit::Hours)
+];
+
+/// Parse an angle in the other angular units; "100 gon", "1600 mil", "0.25 tr" or "9h25m25.05s".
+pub(crate) fn parse_angle_units(source: &str, offset: usize) -> Result<(Angle, AngularUnit), ParseError>
+{
+ let mut cursor = Cursor::new(source, offset);
+ cursor.skip_spaces();
+ let sign_start = cursor.position;
+ let sign = match cursor.peek()
+ {
+  Some('+') =>
+  {
+   cursor.bump();
+   ONE
+  },
+  Some('-') =>
+  {
+   cursor.bump();
+   -ONE
+  },
+  _ => ONE
+ };
+ let (start, end) = cursor.number().ok_or_else(|| match cursor.position > sign_start
+ {
+  true => cursor.error(sign_start, cursor.position, ParseComponent::Sign, "a number after the sign"),
+  false => cursor.error(cursor.position, cursor.position + cursor.next_len(), ParseComponent::Value, "a number")
+ })?;
+ let mut value = source[start..end].parse::<f64>().map_err(|_| cursor.error(start, end, ParseComponent::Value, "a number"))?;
+
+ cursor.skip_spaces();
+ let (unit, length) = ANGULAR_UNIT_WORDS
+  .iter()
+  .find(|(word, _)| starts_with_word(cursor.rest(), word))
+  .map(|&(word, unit)| (unit, word.len()))
+  .ok_or_else(|| {
+   let end = cursor.position + cursor.rest().trim_end().len();
+   cursor.error(cursor.position, end, ParseComponent::Unit, "gon, mil, tr or h")
+  })?;
+ cursor.position += length;
+
+ // The minutes and the seconds of the time after the hours
+ if unit == AngularUnit::Hours
+ {
+  let components = [
+   (['m', 'ᵐ'], ParseComponent::Minutes, F60, "m after the minutes", "the minutes less than 60"),
+   (['s', 'ˢ'], ParseComponent::Seconds, F60 * F60, "s after the seconds", "the seconds less than 60")
+  ];
+  for (symbols, component, per_hour, expected_symbol, expected_value) in components
+  {
+   let before_spaces = cursor.position;
+   cursor.skip_spaces();
+   let (start, end) = match cursor.number()
+   {
+    Some(span) => span,
+    None =>
+    {
+     cursor.position = before_spaces;
+     break;
+    }
+   };
+   let minutes_or_seconds = source[start..end].parse::<f64>().map_err(|_| cursor.error(start, end, component, "a number"))?;
+   if !cursor.peek().is_some_and(|c| symbols.contains(&c.to_ascii_lowercase()))
+   {
+    return Err(cursor.error(start, end, component, expected_symbol));
+   }
+   if minutes_or_seconds >= F60
+   {
+    return Err(cursor.error(start, end, component, expected_value));
+   }
+   cursor.bump();
+   value += minutes_or_seconds / per_hour;
+  }
+ }
+
+ check_end(source, offset, cursor.position, &ParseOptions::strict())?;
+ Ok((Angle::from_units(sign * value, unit), unit))
+}
+
+/// An axis of the compact notations; eg, "430343N", "N4303.725" or "+1412115"
+struct CompactAxis
+{
+ sign: f64,
+ direction: AngleDirectionNotation,
+ /// The byte span of the integer digits
+ integer: (usize, usize),
+ /// The byte span of the decimal places with the "."; empty if none
+ fraction: (usize, usize),
+ start: usize,
+ end: usize
+}
+
+fn compact_hemisphere(c: Option<char>) -> Option<(f64, AngleDirectionNotation)>
+{
+ match c
+ {
+  Some('N') => Some((ONE, AngleDirectionNotation::Latitude)),
+  Some('S') => Some((-ONE, AngleDirectionNotation::Latitude)),
+  Some('E') => Some((ONE, AngleDirectionNotation::Longitude)),
+  Some('W') => Some((-ONE, AngleDirectionNotation::Longitude)),
+  Some('+') => Some((ONE, AngleDirectionNotation::None)),
+  Some('-') => Some((-ONE, AngleDirectionNotation::None)),
+  _ => None
+ }
+}
+
+fn parse_compact_axis(cursor: &mut Cursor) -> Result<CompactAxis, ParseError>
+{
+ let start = cursor.position;
+ let prefix = compact_hemisphere(cursor.peek());
+ if prefix.is_some()
+ {
+  cursor.bump();
+ }
+
+ let integer_start = cursor.position;
+ cursor.skip_digits();
+ let integer = (integer_start, cursor.position);
+ if integer.0 == integer.1
+ {
+  return Err(cursor.error(
+   cursor.position,
+   cursor.position + cursor.next_len(),
+   ParseComponent::Degrees,
+   "the digits; eg, DDMMSS or DDDMMSS"
+  ));
+ }
+ let fraction_start = cursor.position;
+ if cursor.starts_with_fraction()
+ {
+  cursor.bump();
+  cursor.skip_digits();
+ }
+ let fraction = (fraction_start, cursor.position);
+
+ // The hemisphere after the digits if not before; "+" and "-" are only before
+ let hemisphere = match prefix
+ {
+  Some(prefix) => prefix,
+  None => match compact_hemisphere(cursor.peek()).filter(|(_, direction)| *direction != AngleDirectionNotation::None)
+  {
+   Some(suffix) =>
+   {
+    cursor.bump();
+    suffix
+   },
+   None => (ONE, AngleDirectionNotation::None)
+  }
+ };
+
+ Ok(CompactAxis {
+  sign: hemisphere.0,
+  direction: hemisphere.1,
+  integer,
+  fraction,
+  start,
+  end: cursor.position
+ })
+}
+
+/// return the angle of the axis; the integer digits are DD[MM[SS]] for the latitude, DDD[MM[SS]] for the longitude
+fn compact_angle(cursor: &Cursor, axis: &CompactAxis, direction: AngleDirectionNotation) -> Result<Angle, ParseError>
+{
+ let (width, limit, expected) = match direction
+ {
+  AngleDirectionNotation::Longitude => (3, F180, "3, 5 or 7 digits of the longitude; DDD, DDDMM or DDDMMSS"),
+  _ => (2, F90, "2, 4 or 6 digits of the latitude; DD, DDMM or DDMMSS")
+ };
+ let (start, end) = axis.integer;
+ let digits = &cursor.source[start..end];
+ if digits.len() != width && digits.len() != width + 2 && digits.len() != width + 4
+ {
+  return Err(cursor.error(start, end, ParseComponent::Degrees, expected));
+ }
+
+ // The decimal places belong to the last component.
+ let fraction = &cursor.source[axis.fraction.0..axis.fraction.1];
+ let fraction = if fraction.is_empty() { ZERO } else { fraction.parse::<f64>().unwrap_or(ZERO) };
+ let components = [(0, width), (width, width + 2), (width + 2, width + 4)];
+ let mut degrees = ZERO;
+ for (index, &(component_start, component_end)) in components.iter().enumerate().take_while(|(_, (s, _))| *s < digits.len())
+ {
+  let value = digits[component_start..component_end].parse::<f64>().unwrap_or(ZERO);
+  let value = if component_end == digits.len() { value + fraction } else { value };
+  if index > 0 && value >= F60
+  {
+   let (component, expected) = match index
+   {
+    1 => (ParseComponent::Minutes, "the minutes less than 60"),
+    _ => (ParseComponent::Seconds, "the seconds less than 60")
+   };
+   return Err(cursor.error(start + component_start, start + component_end, component, expected));
+  }
+  degrees += value / F60.powi(index as i32);
+ }
+
+ if degrees > limit
+ {
+  let expected = if limit == F90 { "a latitude within ±90°" } else { "a longitude within ±180°" };
+  return Err(cursor.error(axis.start, axis.end, ParseComponent::Degrees, expected));
+ }
+ Ok(Angle::from_degrees(axis.sign * degrees))
+}
+
+/// Parse a compact notation; eg, "4303N14121E", "430343N1412115E", "N4303.725E14121.263" or "+430343+1412115".
+///
+/// - The latitude is DD, DDMM or DDMMSS and the longitude is DDD, DDDMM or DDDMMSS; the last may have the decimal places.
+/// - The hemispheres are N, S, E or W before or after the digits, or "+" or "-" before.
+/// - The order is by the hemispheres; otherwise the latitude first.
+pub(crate) fn parse_compact(source: &str, offset: usize) -> Result<LonLat, ParseError>
+{
+ let mut cursor = Cursor::new(source, offset);
+ cursor.skip_spaces();
+ let first = parse_compact_axis(&mut cursor)?;
+ cursor.skip_spaces();
+ let second = parse_compact_axis(&mut cursor)?;
+ cursor.skip_spaces();
+ if cursor.peek().is_some()
+ {
+  return Err(cursor.error(cursor.position, source.trim_end().len(), ParseComponent::End, "the end of the compact notation"));
+ }
+
+ use AngleDirectionNotation::*;
+ let (lon, lat) = match (first.direction, second.direction)
+ {
+  (Latitude, Latitude) | (Longitude, Longitude) =>
+  {
+   return Err(cursor.error(second.start, second.end, ParseComponent::Hemisphere, "the other axis; N or S and E or W"))
+  },
+  (Longitude, _) | (None, Latitude) => (first, second),
+  _ => (second, first)
+ };
+ Ok(LonLat::new(compact_angle(&cursor, &lon, Longitude)?, compact_angle(&cursor, &lat, Latitude)?))
+}
+
+/// return the value of a kanji numeral; ( digit or unit, is unit )
+fn kanji_numeral(c: char) -> Option<(u64, bool)>
+{
+ Some(match c
+ {
+  '〇' | '零' => (0, false),
+  '一' => (1, false),
+  '二' => (2, false),
+  '三' => (3, false),
+  '四' => (4, false),
+  '五' => (5, false),
+  '六' => (6, false),
+  '七' => (7, false),
+  '八' => (8, false),
+  '九' => (9, false),
+  '十' => (10, true),
+  '百' => (100, true),
+  '千' => (1000, true),
+  _ => return None
+ })
+}
+
+/// Push the digits of a run of the kanji numerals; "百四十一" -> "141", "四三" -> "43", "〇三" -> "03"
+///
+/// return false if the value overflows; nothing is pushed
+fn push_kanji_numerals(run: &[(u64, bool)], normalized: &mut String) -> bool
+{
+ if !run.iter().any(|&(_, is_unit)| is_unit)
+ {
+  normalized.extend(run.iter().map(|&(digit, _)| char::from(b'0' + digit as u8)));
+  return true;
+ }
+ let (mut total, mut current) = (0u64, 0u64);
+ for &(value, is_unit) in run
+ {
+  let next = match is_unit
+  {
+   true => current.max(1).checked_mul(value).and_then(|n| total.checked_add(n)).map(|n| (n, 0)),
+   false => current.checked_mul(10).and_then(|n| n.checked_add(value)).map(|n| (total, n))
+  };
+  match next
+  {
+   Some(next) => (total, current) = next,
+   None => return false
+  }
+ }
+ match total.checked_add(current)
+ {
+  Some(value) =>
+  {
+   normalized.push_str(&value.to_string());
+   true
+  },
+  None => false
+ }
+}
+
+/// Normalize the full-width characters(NFKC; eg, "４３．５") and the kanji numerals(eg, "四十三") to ASCII.
+///
+/// return None if nothing to normalize; otherwise ( the normalized source, the byte offsets in the source of the bytes in it and the end )
+fn normalize_cjk(source: &str) -> Option<(String, Vec<usize>)>
+{
+ let needs_normalization = |c: char| matches!(c, '\u{FF01}'..='\u{FF5E}' | '\u{3000}' | '・') || kanji_numeral(c).is_some();
+ if !source.contains(needs_normalization)
+ {
+  return None;
+ }
+
+ let mut normalized = String::with_capacity(source.len());
+ let mut map = Vec::with_capacity(source.len() + 1);
+ let mut chars = source.char_indices().peekable();
+ while let Some((index, c)) = chars.next()
+ {
+  let before = normalized.len();
+  if let Some(numeral) = kanji_numeral(c)
+  {
+   let mut run = vec![numeral];
+   while let Some(numeral) = chars.peek().and_then(|&(_, c)| kanji_numeral(c))
+   {
+    run.push(numeral);
+    chars.next();
+   }
+   if !push_kanji_numerals(&run, &mut normalized)
+   {
+    // Keep the overflowing run as is, thus it is not a number for the parsers
+    let end = chars.peek().map_or(source.len(), |&(end, _)| end);
+    normalized.push_str(&source[index..end]);
+   }
+  }
+  else
+  {
+   let c = match c
+   {
+    '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+    '\u{3000}' => ' ',
+    // The decimal point between the kanji numerals; eg, "四十三・五"
+    '・' if normalized.ends_with(|c: char| c.is_ascii_digit()) && chars.peek().is_some_and(|&(_, c)| kanji_numeral(c).is_some()) => '.',
+    _ => c
+   };
+   normalized.push(c);
+  }
+  map.extend(std::iter::repeat(index).take(normalized.len() - before));
+ }
+ map.push(source.len());
+ Some((normalized, map))
+}
+
+/// Parse the source normalized if any of the locales `normalizes_cjk`; the spans of the errors are in the source.
+pub(crate) fn normalized<T>(
+ source: &str,
+ offset: usize,
+ options: &ParseOptions,
+ parse: impl FnOnce(&str, usize) -> Result<T, ParseError>
+) -> Result<T, ParseError>
+{
+ let normalized = match options.locales.iter().any(|locale| locale.normalizes_cjk())
+ {
+  true => normalize_cjk(source),
+  false => None
+ };
+ match normalized
+ {
+  Some((normalized, map)) => parse(&normalized, 0).map_err(|e| {
+   let (start, end) = (map[e.span.start], map[e.span.end]);
+   ParseError {
+    span: offset + start..offset + end,
+    found: source[start..end].into(),
+    ..e
+   }
+  }),
+  None => parse(source, offset)
+ }
+}
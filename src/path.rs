@@ -0,0 +1,255 @@
+use crate::prelude::*;
+use measurements::Length;
+
+/// Iteration limit of the projection onto a geodesic segment
+pub const PATH_PROJECTION_MAXIMUM_ITERATIONS: usize = 16;
+/// Convergence threshold [m] of the projection onto a geodesic segment
+pub const PATH_PROJECTION_CONVERGENCE_THRESHOLD: f64 = 1.0e-6;
+
+/// A polyline on the ellipsoid; the vertices are connected by geodesics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LonLatPath
+{
+ pub vertices: Vec<LonLat>
+}
+
+/// A position on a path; the result of `LonLatPath::locate`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LonLatPathPosition
+{
+ /// The nearest point on the path
+ pub point: LonLat,
+ /// The index of the segment; the segment is from `vertices[segment_index]` to `vertices[segment_index + 1]`
+ pub segment_index: usize,
+ /// The distance along the path from the start to `point`; aka chainage or station
+ pub along_track_distance: Length,
+ /// The signed distance from `point` to the target; positive on the right side of the path
+ pub cross_track_distance: Length
+}
+
+impl LonLatPath
+{
+ pub fn new(vertices: Vec<LonLat>) -> Self
+ {
+  Self {
+   vertices
+  }
+ }
+
+ pub fn from_lonlats<'a, T: 'a + LonLatGettable, I: IntoIterator<Item = &'a T>>(sources: I) -> Self
+ {
+  Self::new(sources.into_iter().map(|s| s.as_lonlat()).collect())
+ }
+
+ pub fn segments(&self) -> impl Iterator<Item = (&LonLat, &LonLat)>
+ {
+  self.vertices.iter().zip(self.vertices.iter().skip(1))
+ }
+
+ pub fn bounds(&self) -> Option<LonLatBounds>
+ {
+  LonLatBounds::from_lonlats(&self.vertices)
+ }
+
+ pub fn length(&self, ellipsoid: &Ellipsoid) -> Result<Length, CivilEngineeringLocationError>
+ {
+  self
+   .segments()
+   .try_fold(ZERO, |sum, (a, b)| Ok(sum + a.geodesic_distance(b, ellipsoid)?.as_meters()))
+   .map(Length::from_meters)
+ }
+
+ /// return the chainages(stations) of the vertices; the first is always 0 [m]
+ pub fn vertex_chainages(&self, ellipsoid: &Ellipsoid) -> Result<Vec<Length>, CivilEngineeringLocationError>
+ {
+  let mut chainages = Vec::with_capacity(self.vertices.len());
+  let mut sum = ZERO;
+  if !self.vertices.is_empty()
+  {
+   chainages.push(Length::from_meters(sum));
+  }
+  for (a, b) in self.segments()
+  {
+   sum += a.geodesic_distance(b, ellipsoid)?.as_meters();
+   chainages.push(Length::from_meters(sum));
+  }
+  Ok(chainages)
+ }
+
+ /// return the point at the distance along the path; the distance is clamped to [0..length]
+ pub fn point_at_distance(&self, distance: Length, ellipsoid: &Ellipsoid) -> Result<LonLat, CivilEngineeringLocationError>
+ {
+  let first = self.vertices.first().ok_or(CivilEngineeringLocationError::EmptyPath)?;
+  let mut remaining = distance.as_meters();
+  if remaining <= ZERO
+  {
+   return Ok(*first);
+  }
+
+  for (a, b) in self.segments()
+  {
+   let inverse = a.geodesic_inverse(b, ellipsoid)?;
+   let length = inverse.distance.as_meters();
+   if remaining <= length
+   {
+    return Ok(a.geodesic_destination(inverse.initial_azimuth, Length::from_meters(remaining), ellipsoid));
+   }
+   remaining -= length;
+  }
+
+  Ok(*self.vertices.last().unwrap_or(first))
+ }
+
+ /// return the position on the path nearest to the target
+ pub fn locate<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Result<LonLatPathPosition, CivilEngineeringLocationError>
+ {
+  let first = self.vertices.first().ok_or(CivilEngineeringLocationError::EmptyPath)?;
+
+  if self.vertices.len() == 1
+  {
+   let inverse = first.geodesic_inverse(target, ellipsoid)?;
+   return Ok(LonLatPathPosition {
+    point: *first,
+    segment_index: 0,
+    along_track_distance: Length::from_meters(ZERO),
+    cross_track_distance: inverse.distance
+   });
+  }
+
+  let mut nearest: Option<LonLatPathPosition> = None;
+  let mut chainage = ZERO;
+  for (index, (a, b)) in self.segments().enumerate()
+  {
+   let (point, along, cross, length) = project_onto_segment(a, b, target, ellipsoid)?;
+   let is_nearer = match &nearest
+   {
+    Some(n) => cross.abs() < n.cross_track_distance.as_meters().abs(),
+    None => true
+   };
+   if is_nearer
+   {
+    nearest = Some(LonLatPathPosition {
+     point,
+     segment_index: index,
+     along_track_distance: Length::from_meters(chainage + along),
+     cross_track_distance: Length::from_meters(cross)
+    });
+   }
+   chainage += length;
+  }
+
+  nearest.ok_or(CivilEngineeringLocationError::EmptyPath)
+ }
+
+ pub fn nearest_point<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Result<LonLat, CivilEngineeringLocationError>
+ {
+  self.locate(target, ellipsoid).map(|p| p.point)
+ }
+
+ /// return the signed distance from the path to the target; positive on the right side of the path
+ pub fn cross_track_distance<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Result<Length, CivilEngineeringLocationError>
+ {
+  self.locate(target, ellipsoid).map(|p| p.cross_track_distance)
+ }
+
+ /// return the distance along the path to the nearest point of the target; aka chainage or station
+ pub fn along_track_distance<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Result<Length, CivilEngineeringLocationError>
+ {
+  self.locate(target, ellipsoid).map(|p| p.along_track_distance)
+ }
+}
+
+/// return ( the nearest point, distance along the segment [m], signed cross-track distance [m], length of the segment [m] )
+fn project_onto_segment<T: LonLatGettable>(
+ a: &LonLat,
+ b: &LonLat,
+ target: &T,
+ ellipsoid: &Ellipsoid
+) -> Result<(LonLat, f64, f64, f64), CivilEngineeringLocationError>
+{
+ let segment = a.geodesic_inverse(b, ellipsoid)?;
+ let length = segment.distance.as_meters();
+
+ // Initial guess on the sphere
+ let mut s = if length > ZERO
+ {
+  let d13 = a.great_circle_central_angle(target);
+  let d12 = a.great_circle_central_angle(b);
+  let t = (a.great_circle_initial_bearing(target) - a.great_circle_initial_bearing(b)).as_radians();
+  let dxt = (d13.sin() * t.sin()).clamp(-ONE, ONE).asin();
+  let dat = (d13.cos() / dxt.cos()).clamp(-ONE, ONE).acos() * t.cos().signum();
+  (dat / d12 * length).clamp(ZERO, length)
+ }
+ else
+ {
+  ZERO
+ };
+
+ // Refinement on the ellipsoid; move along the segment by the projection of the distance to the target.
+ let mut point = *a;
+ let mut along = s;
+ let mut cross = ZERO;
+ for _ in 0..PATH_PROJECTION_MAXIMUM_ITERATIONS
+ {
+  let direct = a.geodesic_direct(segment.initial_azimuth, Length::from_meters(s), ellipsoid);
+  // Snap to the vertices at the both ends
+  point = if s <= ZERO
+  {
+   *a
+  }
+  else if s >= length
+  {
+   *b
+  }
+  else
+  {
+   direct.destination
+  };
+  along = s;
+  let to_target = point.geodesic_inverse(target, ellipsoid)?;
+  let t = (to_target.initial_azimuth - direct.final_azimuth).as_radians();
+  let distance = to_target.distance.as_meters();
+  cross = if t.sin() < ZERO { -distance } else { distance };
+
+  let ds = distance * t.cos();
+  let s_next = (s + ds).clamp(ZERO, length);
+  if (s_next - s).abs() < PATH_PROJECTION_CONVERGENCE_THRESHOLD
+  {
+   break;
+  }
+  s = s_next;
+ }
+
+ Ok((point, along, cross, length))
+}
+
+impl From<LonLatPath> for Vec<LonLat>
+{
+ fn from(source: LonLatPath) -> Self
+ {
+  source.vertices
+ }
+}
+
+impl From<Vec<LonLat>> for LonLatPath
+{
+ fn from(source: Vec<LonLat>) -> Self
+ {
+  Self::new(source)
+ }
+}
+
+impl LonLatPathPosition
+{
+ /// return the chainage(station) of the position; an alias of `along_track_distance`
+ pub fn chainage(&self) -> Length
+ {
+  self.along_track_distance
+ }
+
+ /// return the unsigned distance from the path to the target
+ pub fn offset(&self) -> Length
+ {
+  Length::from_meters(self.cross_track_distance.as_meters().abs())
+ }
+}
@@ -0,0 +1,210 @@
+use crate::prelude::*;
+use measurements::{
+ Angle,
+ Area,
+ Length
+};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum WindingOrder
+{
+ Clockwise,
+ CounterClockwise,
+ None
+}
+
+/// A polygon on the ellipsoid; the ring is closed implicitly.
+///
+/// - The edge model depends on the method; `perimeter` follows the geodesics, `signed_area`, `area`, `winding_order` and
+///   `contains` follow the great-circles.
+/// - The sign of the area follows the winding order; counter-clockwise is positive.
+/// - The interior is the region on the left of a counter-clockwise ring, or on the right of a clockwise ring.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LonLatPolygon
+{
+ pub vertices: Vec<LonLat>
+}
+
+impl LonLatPolygon
+{
+ pub fn new(vertices: Vec<LonLat>) -> Self
+ {
+  let mut vertices = vertices;
+  // An explicitly closed ring is accepted too.
+  if vertices.len() > 1 && vertices.first() == vertices.last()
+  {
+   vertices.pop();
+  }
+  Self {
+   vertices
+  }
+ }
+
+ pub fn from_lonlats<'a, T: 'a + LonLatGettable, I: IntoIterator<Item = &'a T>>(sources: I) -> Self
+ {
+  Self::new(sources.into_iter().map(|s| s.as_lonlat()).collect())
+ }
+
+ /// return the edges as pairs of the vertices including the closing edge
+ pub fn edges(&self) -> impl Iterator<Item = (&LonLat, &LonLat)>
+ {
+  self.vertices.iter().zip(self.vertices.iter().cycle().skip(1))
+ }
+
+ pub fn bounds(&self) -> Option<LonLatBounds>
+ {
+  LonLatBounds::from_lonlats(&self.vertices)
+ }
+
+ /// return the sum of the geodesic distances of the edges.
+ pub fn perimeter(&self, ellipsoid: &Ellipsoid) -> Result<Length, CivilEngineeringLocationError>
+ {
+  if self.vertices.len() < 2
+  {
+   return Ok(Length::from_meters(ZERO));
+  }
+
+  self
+   .edges()
+   .try_fold(ZERO, |sum, (a, b)| Ok(sum + a.geodesic_distance(b, ellipsoid)?.as_meters()))
+   .map(Length::from_meters)
+ }
+
+ /// return the signed area; counter-clockwise is positive.
+ ///
+ /// Note: Computed as the spherical excess on the authalic sphere of the ellipsoid;
+ ///       the edges are approximated by the great-circles on the authalic sphere.
+ pub fn signed_area(&self, ellipsoid: &Ellipsoid) -> Area
+ {
+  if self.vertices.len() < 3
+  {
+   return Area::from_square_meters(ZERO);
+  }
+
+  let authalic = Authalic::new(ellipsoid);
+
+  // Σ (Δλ - E); E := the spherical excess of the trapezoid between the edge and the equator.
+  let excess: f64 = self
+   .edges()
+   .map(|(a, b)| {
+    let dlon = (b.lon - a.lon).as_normalize_negative_pi_positive_pi().as_radians();
+    let t1 = (authalic.latitude(a.lat.as_radians()) / 2.0).tan();
+    let t2 = (authalic.latitude(b.lat.as_radians()) / 2.0).tan();
+    let e = 2.0 * ((dlon / 2.0).tan() * (t1 + t2)).atan2(ONE + t1 * t2);
+    dlon - e
+   })
+   .sum();
+
+  Area::from_square_meters(excess * authalic.radius * authalic.radius)
+ }
+
+ /// return the absolute area; the edges are the great-circles on the authalic sphere as `signed_area`.
+ pub fn area(&self, ellipsoid: &Ellipsoid) -> Area
+ {
+  Area::from_square_meters(self.signed_area(ellipsoid).as_square_meters().abs())
+ }
+
+ pub fn winding_order(&self) -> WindingOrder
+ {
+  // The winding order does not depend on the flattening.
+  let area = self.signed_area(&Ellipsoid::sphere(Length::from_meters(ONE))).as_square_meters();
+  if area > ZERO
+  {
+   WindingOrder::CounterClockwise
+  }
+  else if area < ZERO
+  {
+   WindingOrder::Clockwise
+  }
+  else
+  {
+   WindingOrder::None
+  }
+ }
+
+ /// return true if the target is inside or on the boundary.
+ ///
+ /// Note: The winding number of the ring around the target is computed from the great-circle bearings,
+ ///       thus it works across the antimeridian and around the poles.
+ pub fn contains<T: LonLatGettable>(&self, target: &T) -> bool
+ {
+  let order = self.winding_order();
+  if order == WindingOrder::None
+  {
+   return false;
+  }
+
+  let mut sum = ZERO;
+  for (a, b) in self.edges()
+  {
+   if a.great_circle_central_angle(target) < POLYGON_BOUNDARY_TOLERANCE
+   {
+    return true;
+   }
+   let ba = target.great_circle_initial_bearing(a).as_radians();
+   let bb = target.great_circle_initial_bearing(b).as_radians();
+   let turn = Angle::from_radians(bb - ba).as_normalize_negative_pi_positive_pi().as_radians();
+   // On the edge, the bearings are opposite.
+   if (turn.abs() - PI).abs() < POLYGON_BOUNDARY_TOLERANCE
+   {
+    return true;
+   }
+   sum += turn;
+  }
+
+  // Bearings turn clockwise(positive) around the target inside a clockwise ring.
+  match order
+  {
+   WindingOrder::Clockwise => sum > PI,
+   WindingOrder::CounterClockwise => sum < -PI,
+   WindingOrder::None => false
+  }
+ }
+}
+
+/// Tolerance [rad] for the boundary detection in `LonLatPolygon::contains`; 1.0e-12 [rad] ≈ 0.006 [mm]
+pub const POLYGON_BOUNDARY_TOLERANCE: f64 = 1.0e-12;
+
+/// The authalic(equal-area) sphere of an ellipsoid.
+struct Authalic
+{
+ radius: f64,
+ e: f64,
+ qp: f64
+}
+
+impl Authalic
+{
+ fn new(ellipsoid: &Ellipsoid) -> Self
+ {
+  let e = ellipsoid.e2().sqrt();
+  let mut authalic = Self {
+   radius: ellipsoid.a,
+   e,
+   qp: 2.0
+  };
+  if e > ZERO
+  {
+   authalic.qp = authalic.q(PI_DIV_2);
+   authalic.radius = ellipsoid.a * (authalic.qp / 2.0).sqrt();
+  }
+  authalic
+ }
+
+ fn q(&self, lat: f64) -> f64
+ {
+  let e = self.e;
+  let s = lat.sin();
+  (ONE - e * e) * (s / (ONE - e * e * s * s) - ONE / (2.0 * e) * ((ONE - e * s) / (ONE + e * s)).ln())
+ }
+
+ /// return authalic latitude [rad]
+ fn latitude(&self, lat: f64) -> f64
+ {
+  if self.e == ZERO
+  {
+   return lat;
+  }
+  (self.q(lat) / self.qp).clamp(-ONE, ONE).asin()
+ }
+}
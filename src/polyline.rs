@@ -0,0 +1,174 @@
+use crate::prelude::*;
+use measurements::Angle;
+
+/// The maximum digits of `PolylinePrecision::Digits`; the values of ±180° × 1.0e10 fit in the 62 bits of the format
+pub const POLYLINE_MAXIMUM_DIGITS: u8 = 10;
+
+/// The precision of the encoded polyline algorithm format.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub enum PolylinePrecision
+{
+ /// 1.0e-5 [deg]; Google Maps
+ #[default]
+ E5,
+ /// 1.0e-6 [deg]; eg, OSRM and Valhalla
+ E6,
+ /// 1.0e-{digits} [deg]; up to `POLYLINE_MAXIMUM_DIGITS`
+ Digits(u8)
+}
+
+impl PolylinePrecision
+{
+ /// return the factor of the degrees; an error if the digits are more than `POLYLINE_MAXIMUM_DIGITS`
+ pub fn factor(&self) -> Result<f64, CivilEngineeringLocationError>
+ {
+  match self
+  {
+   PolylinePrecision::E5 => Ok(1.0e5),
+   PolylinePrecision::E6 => Ok(1.0e6),
+   PolylinePrecision::Digits(digits) if *digits <= POLYLINE_MAXIMUM_DIGITS => Ok(10f64.powi(*digits as i32)),
+   PolylinePrecision::Digits(digits) => Err(CivilEngineeringLocationError::EncodedPolylinePrecisionOutOfRange(*digits))
+  }
+ }
+}
+
+/// The encoded polyline algorithm format; the values are ordered as lat, lon.
+pub trait ToStringEncodedPolyline
+{
+ fn to_string_encoded_polyline(&self, precision: PolylinePrecision) -> Result<String, CivilEngineeringLocationError>;
+}
+
+pub trait FromEncodedPolylineStr: Sized
+{
+ fn from_encoded_polyline_str(source: &str, precision: PolylinePrecision) -> Result<Self, CivilEngineeringLocationError>;
+}
+
+impl<T: LonLatGettable> ToStringEncodedPolyline for [T]
+{
+ fn to_string_encoded_polyline(&self, precision: PolylinePrecision) -> Result<String, CivilEngineeringLocationError>
+ {
+  let factor = precision.factor()?;
+  let mut encoded = String::with_capacity(self.len() * 12);
+  let (mut previous_lat, mut previous_lon) = (0i64, 0i64);
+
+  for (index, lonlat) in self.iter().enumerate()
+  {
+   let lat = quantize(lonlat.get_lat().as_degrees(), factor, index)?;
+   let lon = quantize(lonlat.get_lon().as_degrees(), factor, index)?;
+   let out_of_range = || CivilEngineeringLocationError::EncodedPolylineValueOutOfRange(index);
+   encode_value(lat.checked_sub(previous_lat).ok_or_else(out_of_range)?, &mut encoded);
+   encode_value(lon.checked_sub(previous_lon).ok_or_else(out_of_range)?, &mut encoded);
+   previous_lat = lat;
+   previous_lon = lon;
+  }
+
+  Ok(encoded)
+ }
+}
+
+impl ToStringEncodedPolyline for LonLatPath
+{
+ fn to_string_encoded_polyline(&self, precision: PolylinePrecision) -> Result<String, CivilEngineeringLocationError>
+ {
+  self.vertices.to_string_encoded_polyline(precision)
+ }
+}
+
+impl FromEncodedPolylineStr for Vec<LonLat>
+{
+ fn from_encoded_polyline_str(source: &str, precision: PolylinePrecision) -> Result<Self, CivilEngineeringLocationError>
+ {
+  let factor = precision.factor()?;
+  let mut lonlats = Vec::with_capacity(source.len() / 8);
+  let mut bytes = source.bytes().enumerate().peekable();
+  let (mut lat, mut lon) = (0i64, 0i64);
+
+  while bytes.peek().is_some()
+  {
+   let (index, delta) = decode_value(&mut bytes, source)?;
+   lat = lat.checked_add(delta).ok_or(CivilEngineeringLocationError::EncodedPolylineOverflow(index))?;
+   if bytes.peek().is_none()
+   {
+    Err(CivilEngineeringLocationError::EncodedPolylineMissingLongitude(source.len()))?;
+   }
+   let (index, delta) = decode_value(&mut bytes, source)?;
+   lon = lon.checked_add(delta).ok_or(CivilEngineeringLocationError::EncodedPolylineOverflow(index))?;
+   lonlats.push(LonLat::new(
+    Angle::from_degrees(lon as f64 / factor),
+    Angle::from_degrees(lat as f64 / factor)
+   ));
+  }
+
+  Ok(lonlats)
+ }
+}
+
+impl FromEncodedPolylineStr for LonLatPath
+{
+ fn from_encoded_polyline_str(source: &str, precision: PolylinePrecision) -> Result<Self, CivilEngineeringLocationError>
+ {
+  Vec::<LonLat>::from_encoded_polyline_str(source, precision).map(LonLatPath::new)
+ }
+}
+
+/// return the degrees × factor; index := the index of the vertex for the error
+fn quantize(degrees: f64, factor: f64, index: usize) -> Result<i64, CivilEngineeringLocationError>
+{
+ if degrees.is_nan()
+ {
+  Err(CivilEngineeringLocationError::Nan)?;
+ }
+ if degrees.is_infinite()
+ {
+  Err(CivilEngineeringLocationError::Infinite)?;
+ }
+ // The deltas of the values within ±2^61 fit in the 62 bits of the zig-zag encoding.
+ let value = (degrees * factor).round();
+ if value.abs() >= (1u64 << 61) as f64
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylineValueOutOfRange(index))?;
+ }
+ Ok(value as i64)
+}
+
+fn encode_value(value: i64, encoded: &mut String)
+{
+ // zig-zag; the sign goes to the least significant bit
+ let mut value = ((value << 1) ^ (value >> 63)) as u64;
+ while value >= 0x20
+ {
+  encoded.push((((value & 0x1f) | 0x20) as u8 + 63) as char);
+  value >>= 5;
+ }
+ encoded.push((value as u8 + 63) as char);
+}
+
+/// return ( the index of the first byte, the value )
+fn decode_value<I: Iterator<Item = (usize, u8)>>(bytes: &mut I, source: &str) -> Result<(usize, i64), CivilEngineeringLocationError>
+{
+ let mut value = 0u64;
+ let mut shift = 0u32;
+ let mut start = None;
+ loop
+ {
+  let (index, byte) = bytes.next().ok_or(CivilEngineeringLocationError::EncodedPolylineUnexpectedEnd(source.len()))?;
+  start.get_or_insert(index);
+  if !(63..=126).contains(&byte)
+  {
+   let character = source[index..].chars().next().unwrap_or_default();
+   Err(CivilEngineeringLocationError::EncodedPolylineInvalidCharacter(index, character))?;
+  }
+  if shift + 5 > 64
+  {
+   Err(CivilEngineeringLocationError::EncodedPolylineOverflow(index))?;
+  }
+  let chunk = (byte - 63) as u64;
+  value |= (chunk & 0x1f) << shift;
+  shift += 5;
+  if chunk < 0x20
+  {
+   break;
+  }
+ }
+ Ok((start.unwrap_or(source.len()), ((value >> 1) as i64) ^ -((value & 1) as i64)))
+}
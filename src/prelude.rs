@@ -1,6 +1,24 @@
 pub use crate::{
+ bounds::*,
+ buffer::*,
+ compact::*,
  dimensions::*,
+ ellipsoid::*,
+ geodesic::*,
+ index::*,
+ interpolation::*,
+ intersection::*,
  error::*,
+ extract::*,
+ format::*,
+ locale::*,
  location::*,
- lonlat::*
+ lonlat::*,
+ map_url::*,
+ parse::*,
+ path::*,
+ polygon::*,
+ polyline::*,
+ rhumb::*,
+ simplify::*
 };
@@ -0,0 +1,138 @@
+use crate::prelude::*;
+use measurements::{
+ Angle,
+ Length
+};
+
+/// Threshold [rad] of the isometric latitude difference to treat a rhumb line as an east-west course
+pub const RHUMB_EAST_WEST_THRESHOLD: f64 = 1.0e-12;
+
+/// The solution of the rhumb line inverse problem.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RhumbInverse
+{
+ pub distance: Length,
+ /// Constant bearing := [-π..π); clockwise from the north
+ pub bearing: Angle
+}
+
+/// Rhumb lines(loxodromes) on an ellipsoid; use `Ellipsoid::sphere` for a sphere.
+///
+/// Note: The shorter way in longitude is chosen, thus the courses across the antimeridian are handled naturally.
+pub trait RhumbLine: LonLatGettable
+{
+ fn rhumb_inverse<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> RhumbInverse;
+
+ /// Note: Returns `RhumbLineBeyondPole` if the course reaches a pole before the distance.
+ fn rhumb_destination(&self, bearing: Angle, distance: Length, ellipsoid: &Ellipsoid) -> Result<LonLat, CivilEngineeringLocationError>;
+
+ fn rhumb_distance<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Length
+ {
+  self.rhumb_inverse(target, ellipsoid).distance
+ }
+
+ fn rhumb_bearing<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> Angle
+ {
+  self.rhumb_inverse(target, ellipsoid).bearing
+ }
+}
+
+impl<S: LonLatGettable> RhumbLine for S
+{
+ fn rhumb_inverse<T: LonLatGettable>(&self, target: &T, ellipsoid: &Ellipsoid) -> RhumbInverse
+ {
+  let (lat1, lat2) = (self.get_lat().as_radians(), target.get_lat().as_radians());
+  let dlon = (target.get_lon() - self.get_lon()).as_normalize_negative_pi_positive_pi().as_radians();
+  let dpsi = isometric_latitude(lat2, ellipsoid) - isometric_latitude(lat1, ellipsoid);
+
+  let bearing = dlon.atan2(dpsi);
+  let distance = if dpsi.abs() < RHUMB_EAST_WEST_THRESHOLD
+  {
+   (dlon * parallel_radius(lat1, ellipsoid)).abs()
+  }
+  else
+  {
+   (meridian_distance(lat2, ellipsoid) - meridian_distance(lat1, ellipsoid)) / bearing.cos()
+  };
+
+  RhumbInverse {
+   distance: Length::from_meters(distance),
+   bearing: Angle::from_radians(bearing).as_normalize_negative_pi_positive_pi()
+  }
+ }
+
+ fn rhumb_destination(&self, bearing: Angle, distance: Length, ellipsoid: &Ellipsoid) -> Result<LonLat, CivilEngineeringLocationError>
+ {
+  let lat1 = self.get_lat().as_radians();
+  let theta = bearing.as_radians();
+  let d = distance.as_meters();
+
+  let m2 = meridian_distance(lat1, ellipsoid) + d * theta.cos();
+  if m2.abs() > meridian_distance(PI_DIV_2, ellipsoid)
+  {
+   Err(CivilEngineeringLocationError::RhumbLineBeyondPole)?;
+  }
+  let lat2 = latitude_from_meridian_distance(m2, lat1, ellipsoid);
+
+  let dpsi = isometric_latitude(lat2, ellipsoid) - isometric_latitude(lat1, ellipsoid);
+  let dlon = if dpsi.abs() < RHUMB_EAST_WEST_THRESHOLD
+  {
+   d * theta.sin() / parallel_radius(lat1, ellipsoid)
+  }
+  else
+  {
+   theta.tan() * dpsi
+  };
+
+  Ok(LonLat::new(
+   Angle::from_radians(self.get_lon().as_radians() + dlon).as_normalize_negative_pi_positive_pi(),
+   Angle::from_radians(lat2)
+  ))
+ }
+}
+
+/// return isometric latitude ψ [rad]
+fn isometric_latitude(lat: f64, ellipsoid: &Ellipsoid) -> f64
+{
+ let e = ellipsoid.e2().sqrt();
+ let s = lat.sin();
+ s.atanh() - e * (e * s).atanh()
+}
+
+/// return the radius of the parallel [m]
+fn parallel_radius(lat: f64, ellipsoid: &Ellipsoid) -> f64
+{
+ let e2 = ellipsoid.e2();
+ ellipsoid.a * lat.cos() / (ONE - e2 * lat.sin().powi(2)).sqrt()
+}
+
+/// return the meridian distance from the equator [m]
+fn meridian_distance(lat: f64, ellipsoid: &Ellipsoid) -> f64
+{
+ let e2 = ellipsoid.e2();
+ let (e4, e6) = (e2 * e2, e2 * e2 * e2);
+ ellipsoid.a
+  * ((ONE - e2 / 4.0 - 3.0 * e4 / 64.0 - 5.0 * e6 / 256.0) * lat
+   - (3.0 * e2 / 8.0 + 3.0 * e4 / 32.0 + 45.0 * e6 / 1024.0) * (2.0 * lat).sin()
+   + (15.0 * e4 / 256.0 + 45.0 * e6 / 1024.0) * (4.0 * lat).sin()
+   - (35.0 * e6 / 3072.0) * (6.0 * lat).sin())
+}
+
+/// return the latitude [rad] of the meridian distance; Newton's method
+fn latitude_from_meridian_distance(m: f64, initial: f64, ellipsoid: &Ellipsoid) -> f64
+{
+ let e2 = ellipsoid.e2();
+ let mut lat = initial;
+ for _ in 0..GEODESIC_MAXIMUM_ITERATIONS
+ {
+  // dm/dφ := the meridional radius of curvature
+  let derivative = ellipsoid.a * (ONE - e2) / (ONE - e2 * lat.sin().powi(2)).powf(1.5);
+  let delta = (meridian_distance(lat, ellipsoid) - m) / derivative;
+  lat = (lat - delta).clamp(-PI_DIV_2, PI_DIV_2);
+  if delta.abs() < GEODESIC_CONVERGENCE_THRESHOLD
+  {
+   break;
+  }
+ }
+ lat
+}
@@ -0,0 +1,252 @@
+use crate::prelude::*;
+use measurements::Length;
+use std::{
+ cmp::Ordering,
+ collections::BinaryHeap
+};
+
+/// Path simplification on the sphere of the mean radius of the ellipsoid.
+///
+/// - The first and the last points are always preserved.
+/// - `douglas_peucker`: `tolerance` := the maximum distance from the simplified path to the removed points.
+/// - `visvalingam`: `tolerance` := the side of the square of the minimum effective area; tolerance² is the threshold.
+pub trait Simplify<T>
+{
+ fn simplify_douglas_peucker(&self, tolerance: Length, ellipsoid: &Ellipsoid) -> Vec<T>;
+ fn simplify_visvalingam(&self, tolerance: Length, ellipsoid: &Ellipsoid) -> Vec<T>;
+}
+
+/// Path simplification with the altitude; the deviations are measured in 3-D.
+pub trait SimplifyWithAlt<T>
+{
+ fn simplify_douglas_peucker_with_alt(&self, tolerance: Length, ellipsoid: &Ellipsoid) -> Vec<T>;
+ fn simplify_visvalingam_with_alt(&self, tolerance: Length, ellipsoid: &Ellipsoid) -> Vec<T>;
+}
+
+impl<T: LonLatGettable + Clone> Simplify<T> for [T]
+{
+ fn simplify_douglas_peucker(&self, tolerance: Length, ellipsoid: &Ellipsoid) -> Vec<T>
+ {
+  let radius = ellipsoid.mean_radius();
+  let keep = douglas_peucker(self.len(), tolerance.as_meters(), |a, p, b| {
+   let (horizontal, _) = segment_distance(&self[a], &self[p], &self[b]);
+   horizontal * radius
+  });
+  pick(self, &keep)
+ }
+
+ fn simplify_visvalingam(&self, tolerance: Length, ellipsoid: &Ellipsoid) -> Vec<T>
+ {
+  let radius = ellipsoid.mean_radius();
+  let threshold = tolerance.as_meters().powi(2);
+  let keep = visvalingam(self.len(), threshold, |a, p, b| {
+   let ap = self[a].great_circle_central_angle(&self[p]) * radius;
+   let pb = self[p].great_circle_central_angle(&self[b]) * radius;
+   let ab = self[a].great_circle_central_angle(&self[b]) * radius;
+   triangle_area(ap, pb, ab)
+  });
+  pick(self, &keep)
+ }
+}
+
+impl<T: LonLatGettable + AltGettable + Clone> SimplifyWithAlt<T> for [T]
+{
+ fn simplify_douglas_peucker_with_alt(&self, tolerance: Length, ellipsoid: &Ellipsoid) -> Vec<T>
+ {
+  let radius = ellipsoid.mean_radius();
+  let keep = douglas_peucker(self.len(), tolerance.as_meters(), |a, p, b| {
+   let (horizontal, fraction) = segment_distance(&self[a], &self[p], &self[b]);
+   let (alt_a, alt_b) = (self[a].get_alt().as_meters(), self[b].get_alt().as_meters());
+   let vertical = self[p].get_alt().as_meters() - (alt_a + (alt_b - alt_a) * fraction);
+   (horizontal * radius).hypot(vertical)
+  });
+  pick(self, &keep)
+ }
+
+ fn simplify_visvalingam_with_alt(&self, tolerance: Length, ellipsoid: &Ellipsoid) -> Vec<T>
+ {
+  let radius = ellipsoid.mean_radius();
+  let threshold = tolerance.as_meters().powi(2);
+  let side = |i: usize, j: usize| {
+   let horizontal = self[i].great_circle_central_angle(&self[j]) * radius;
+   horizontal.hypot(self[j].get_alt().as_meters() - self[i].get_alt().as_meters())
+  };
+  let keep = visvalingam(self.len(), threshold, |a, p, b| triangle_area(side(a, p), side(p, b), side(a, b)));
+  pick(self, &keep)
+ }
+}
+
+fn pick<T: Clone>(source: &[T], keep: &[bool]) -> Vec<T>
+{
+ source.iter().zip(keep.iter()).filter(|(_, &k)| k).map(|(s, _)| s.clone()).collect()
+}
+
+/// return ( distance from `p` to the great-circle segment `a`-`b` [rad], fraction of the nearest point along the segment := [0..1] )
+fn segment_distance<A: LonLatGettable, P: LonLatGettable, B: LonLatGettable>(a: &A, p: &P, b: &B) -> (f64, f64)
+{
+ let d_ab = a.great_circle_central_angle(b);
+ let d_ap = a.great_circle_central_angle(p);
+ if d_ab == ZERO
+ {
+  return (d_ap, ZERO);
+ }
+
+ let t = (a.great_circle_initial_bearing(p) - a.great_circle_initial_bearing(b)).as_radians();
+ let xt = (d_ap.sin() * t.sin()).clamp(-ONE, ONE).asin();
+ let at = (d_ap.cos() / xt.cos()).clamp(-ONE, ONE).acos() * t.cos().signum();
+
+ if at <= ZERO
+ {
+  (d_ap, ZERO)
+ }
+ else if at >= d_ab
+ {
+  (b.great_circle_central_angle(p), ONE)
+ }
+ else
+ {
+  (xt.abs(), at / d_ab)
+ }
+}
+
+/// Triangle area from the sides; numerically stable form of Heron's formula
+fn triangle_area(a: f64, b: f64, c: f64) -> f64
+{
+ let mut sides = [a, b, c];
+ sides.sort_by(|x, y| y.partial_cmp(x).unwrap_or(Ordering::Equal));
+ let [a, b, c] = sides;
+ let product = (a + (b + c)) * (c - (a - b)) * (c + (a - b)) * (a + (b - c));
+ product.max(ZERO).sqrt() / 4.0
+}
+
+fn douglas_peucker<F: Fn(usize, usize, usize) -> f64>(len: usize, tolerance: f64, distance: F) -> Vec<bool>
+{
+ let mut keep = vec![false; len];
+ if len == 0
+ {
+  return keep;
+ }
+ keep[0] = true;
+ keep[len - 1] = true;
+
+ // An explicit stack instead of the recursion for the long paths
+ let mut stack = vec![(0, len - 1)];
+ while let Some((first, last)) = stack.pop()
+ {
+  let farthest = (first + 1..last)
+   .map(|i| (i, distance(first, i, last)))
+   .fold(None, |max: Option<(usize, f64)>, (i, d)| {
+    match max
+    {
+     Some((_, m)) if m >= d => max,
+     _ => Some((i, d))
+    }
+   });
+
+  if let Some((index, d)) = farthest
+  {
+   if d > tolerance
+   {
+    keep[index] = true;
+    stack.push((first, index));
+    stack.push((index, last));
+   }
+  }
+ }
+
+ keep
+}
+
+/// A triangle candidate in the Visvalingam-Whyatt heap; the smallest area comes first.
+struct Candidate
+{
+ area: f64,
+ index: usize,
+ version: usize
+}
+
+impl PartialEq for Candidate
+{
+ fn eq(&self, other: &Self) -> bool
+ {
+  self.cmp(other) == Ordering::Equal
+ }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate
+{
+ fn partial_cmp(&self, other: &Self) -> Option<Ordering>
+ {
+  Some(self.cmp(other))
+ }
+}
+
+impl Ord for Candidate
+{
+ fn cmp(&self, other: &Self) -> Ordering
+ {
+  other.area.partial_cmp(&self.area).unwrap_or(Ordering::Equal).then_with(|| other.index.cmp(&self.index))
+ }
+}
+
+fn visvalingam<F: Fn(usize, usize, usize) -> f64>(len: usize, threshold: f64, area: F) -> Vec<bool>
+{
+ let mut keep = vec![true; len];
+ if len < 3
+ {
+  return keep;
+ }
+
+ let mut previous: Vec<usize> = (0..len).map(|i| i.saturating_sub(1)).collect();
+ let mut next: Vec<usize> = (0..len).map(|i| i + 1).collect();
+ let mut versions = vec![0usize; len];
+ let mut heap: BinaryHeap<Candidate> = (1..len - 1)
+  .map(|index| {
+   Candidate {
+    area: area(index - 1, index, index + 1),
+    index,
+    version: 0
+   }
+  })
+  .collect();
+
+ let mut last_area = ZERO;
+ while let Some(candidate) = heap.pop()
+ {
+  if candidate.version != versions[candidate.index]
+  {
+   continue;
+  }
+  if candidate.area >= threshold
+  {
+   break;
+  }
+
+  let index = candidate.index;
+  keep[index] = false;
+  // The effective area never decreases; a removal must not promote the neighbours.
+  last_area = last_area.max(candidate.area);
+
+  let (p, n) = (previous[index], next[index]);
+  next[p] = n;
+  previous[n] = p;
+
+  for &neighbour in &[p, n]
+  {
+   if neighbour == 0 || neighbour == len - 1
+   {
+    continue;
+   }
+   versions[neighbour] += 1;
+   heap.push(Candidate {
+    area: area(previous[neighbour], neighbour, next[neighbour]).max(last_area),
+    index: neighbour,
+    version: versions[neighbour]
+   });
+  }
+ }
+
+ keep
+}
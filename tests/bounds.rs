@@ -0,0 +1,112 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+fn bounds(west: f64, south: f64, east: f64, north: f64) -> LonLatBounds
+{
+ LonLatBounds::new(
+  Angle::from_degrees(west),
+  Angle::from_degrees(south),
+  Angle::from_degrees(east),
+  Angle::from_degrees(north)
+ )
+}
+
+macro_rules! assert_bounds {
+ ($actual:expr, $expected:expr) => {{
+  let (actual, expected) = ($actual, $expected);
+  assert_abs_diff_eq!(actual.west.as_degrees(), expected.0, epsilon = 1.0e-9);
+  assert_abs_diff_eq!(actual.south.as_degrees(), expected.1, epsilon = 1.0e-9);
+  assert_abs_diff_eq!(actual.east.as_degrees(), expected.2, epsilon = 1.0e-9);
+  assert_abs_diff_eq!(actual.north.as_degrees(), expected.3, epsilon = 1.0e-9);
+ }};
+}
+
+#[test]
+fn from_lonlats()
+{
+ let points = vec![lonlat(141.0, 43.0), lonlat(140.0, 42.0), lonlat(142.5, 44.5)];
+ let actual = LonLatBounds::from_lonlats(&points).unwrap();
+ assert_bounds!(actual, (140.0, 42.0, 142.5, 44.5));
+ assert!(!actual.crosses_antimeridian());
+
+ let empty: Vec<LonLat> = vec![];
+ assert!(LonLatBounds::from_lonlats(&empty).is_none());
+}
+
+#[test]
+fn from_lonlats_antimeridian()
+{
+ let points = vec![lonlat(179.0, -17.0), lonlat(-179.5, -16.0), lonlat(178.0, -18.0)];
+ let actual = LonLatBounds::from_lonlats(&points).unwrap();
+ assert_bounds!(actual, (178.0, -18.0, -179.5, -16.0));
+ assert!(actual.crosses_antimeridian());
+ assert_abs_diff_eq!(actual.width().as_degrees(), 2.5, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(actual.center().lon.as_degrees(), 179.25, epsilon = 1.0e-9);
+}
+
+#[test]
+fn contains()
+{
+ let b = bounds(170.0, -10.0, -170.0, 10.0);
+ assert!(b.contains(&lonlat(180.0, 0.0)));
+ assert!(b.contains(&lonlat(-180.0, 0.0)));
+ assert!(b.contains(&lonlat(175.0, 10.0)));
+ assert!(!b.contains(&lonlat(0.0, 0.0)));
+ assert!(!b.contains(&lonlat(175.0, 10.1)));
+
+ assert!(b.contains_bounds(&bounds(175.0, -1.0, -175.0, 1.0)));
+ assert!(!b.contains_bounds(&bounds(160.0, -1.0, -175.0, 1.0)));
+}
+
+#[test]
+fn intersects_and_intersection()
+{
+ let a = bounds(170.0, -10.0, -170.0, 10.0);
+ let b = bounds(-175.0, 0.0, -160.0, 20.0);
+ assert!(a.intersects(&b));
+ let i = a.intersection(&b);
+ assert_eq!(i.len(), 1);
+ assert_bounds!(i[0], (-175.0, 0.0, -170.0, 10.0));
+
+ let c = bounds(0.0, -10.0, 10.0, 10.0);
+ assert!(!a.intersects(&c));
+ assert!(a.intersection(&c).is_empty());
+
+ // Both ends overlap
+ let d = bounds(-175.0, -10.0, 175.0, 10.0);
+ assert_eq!(a.intersection(&d).len(), 2);
+}
+
+#[test]
+fn union()
+{
+ let a = bounds(170.0, -10.0, 175.0, 10.0);
+ let b = bounds(-175.0, 0.0, -170.0, 20.0);
+ assert_bounds!(a.union(&b), (170.0, -10.0, -170.0, 20.0));
+ assert_bounds!(b.union(&a), (170.0, -10.0, -170.0, 20.0));
+
+ let c = bounds(0.0, 0.0, 10.0, 10.0);
+ assert_bounds!(c.extend(&lonlat(20.0, -5.0)), (0.0, -5.0, 20.0, 10.0));
+}
+
+#[test]
+fn expand_by_distance()
+{
+ let b = LonLatBounds::from_lonlat(&lonlat(0.0, 0.0));
+ let actual = b.expand_by_distance(Length::from_kilometers(111.0), &Ellipsoid::WGS84);
+ assert!(actual.north.as_degrees() > 1.0);
+ assert!(actual.west.as_degrees() < -1.0);
+ assert!(actual.contains(&lonlat(0.0, 0.99)));
+
+ let b = LonLatBounds::from_lonlat(&lonlat(179.9, 0.0));
+ assert!(b.expand_by_distance(Length::from_kilometers(100.0), &Ellipsoid::WGS84).crosses_antimeridian());
+
+ let b = LonLatBounds::from_lonlat(&lonlat(10.0, 89.5));
+ let actual = b.expand_by_distance(Length::from_kilometers(100.0), &Ellipsoid::WGS84);
+ assert!(actual.is_full_longitude());
+ assert_abs_diff_eq!(actual.north.as_degrees(), 90.0);
+}
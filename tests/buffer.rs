@@ -0,0 +1,104 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+#[test]
+fn geodesic_circle()
+{
+ let radius = 100_000.0;
+ let center = lonlat(139.767125, 35.681236);
+ let circle = center.geodesic_circle(Length::from_meters(radius), 360, &Ellipsoid::WGS84);
+ assert_eq!(circle.vertices.len(), 360);
+ assert_eq!(circle.winding_order(), WindingOrder::CounterClockwise);
+ for vertex in &circle.vertices
+ {
+  let distance = center.geodesic_distance(vertex, &Ellipsoid::WGS84).unwrap().as_meters();
+  assert_abs_diff_eq!(distance, radius, epsilon = 1.0e-4);
+ }
+ let area = circle.area(&Ellipsoid::WGS84).as_square_meters();
+ assert_abs_diff_eq!(area / (PI * radius * radius), 1.0, epsilon = 1.0e-3);
+ assert!(circle.contains(&center));
+}
+
+#[test]
+fn geodesic_circle_antimeridian_and_pole()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let radius = Length::from_meters(100_000.0);
+
+ let antimeridian = lonlat(180.0, 0.0).geodesic_circle(radius, 64, &ellipsoid);
+ assert!(antimeridian.contains(&lonlat(179.5, 0.0)));
+ assert!(antimeridian.contains(&lonlat(-179.5, 0.0)));
+ assert!(!antimeridian.contains(&lonlat(0.0, 0.0)));
+
+ let pole = lonlat(0.0, 90.0).geodesic_circle(radius, 64, &ellipsoid);
+ assert!(pole.contains(&lonlat(0.0, 90.0)));
+ assert!(pole.contains(&lonlat(123.0, 89.5)));
+ assert!(!pole.contains(&lonlat(123.0, 88.5)));
+ assert_eq!(pole.winding_order(), WindingOrder::CounterClockwise);
+}
+
+#[test]
+fn geodesic_buffer()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let distance = 10_000.0;
+ let path = LonLatPath::new(vec![lonlat(0.0, 0.0), lonlat(1.0, 0.0), lonlat(1.0, 1.0)]);
+ let buffer = path.geodesic_buffer(Length::from_meters(distance), 64, &ellipsoid).unwrap();
+ assert_eq!(buffer.winding_order(), WindingOrder::CounterClockwise);
+
+ // ≈ 5.5 [km] and ≈ 11 [km] from the path
+ assert!(buffer.contains(&lonlat(0.5, 0.05)));
+ assert!(buffer.contains(&lonlat(0.5, -0.05)));
+ assert!(!buffer.contains(&lonlat(0.5, 0.1)));
+ assert!(buffer.contains(&lonlat(-0.05, 0.0)));
+ assert!(!buffer.contains(&lonlat(-0.1, 0.0)));
+ // The inner and the outer side of the turn
+ assert!(buffer.contains(&lonlat(0.95, 0.05)));
+ assert!(buffer.contains(&lonlat(1.05, -0.05)));
+ assert!(!buffer.contains(&lonlat(1.08, -0.08)));
+
+ // The two rectangles, the round caps and the outer quarter-circle, without the inner overlapped square
+ let length = path.length(&ellipsoid).unwrap().as_meters();
+ let expected = 2.0 * distance * length + PI * distance * distance + PI / 4.0 * distance * distance - distance * distance;
+ let actual = buffer.area(&ellipsoid).as_square_meters();
+ assert_abs_diff_eq!(actual / expected, 1.0, epsilon = 1.0e-2);
+}
+
+#[test]
+fn geodesic_buffer_antimeridian_and_pole()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let distance = Length::from_meters(50_000.0);
+
+ let antimeridian = [lonlat(179.0, 10.0), lonlat(-179.0, 10.0)].geodesic_buffer(distance, 64, &ellipsoid).unwrap();
+ assert!(antimeridian.contains(&lonlat(180.0, 10.0)));
+ assert!(antimeridian.contains(&lonlat(-179.5, 10.2)));
+ assert!(!antimeridian.contains(&lonlat(0.0, 10.0)));
+
+ // Over the north pole
+ let pole = [lonlat(0.0, 85.0), lonlat(180.0, 85.0)].geodesic_buffer(distance, 64, &ellipsoid).unwrap();
+ assert!(pole.contains(&lonlat(0.0, 90.0)));
+ assert!(pole.contains(&lonlat(90.0, 89.8)));
+ assert!(!pole.contains(&lonlat(90.0, 85.0)));
+ assert_eq!(pole.winding_order(), WindingOrder::CounterClockwise);
+}
+
+#[test]
+fn geodesic_buffer_point_and_empty()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let distance = Length::from_meters(1_000.0);
+ let point = [lonlat(10.0, 20.0), lonlat(10.0, 20.0)].geodesic_buffer(distance, 32, &ellipsoid).unwrap();
+ assert_eq!(point, lonlat(10.0, 20.0).geodesic_circle(distance, 32, &ellipsoid));
+
+ let empty: [LonLat; 0] = [];
+ match empty.geodesic_buffer(distance, 32, &ellipsoid)
+ {
+  Err(CivilEngineeringLocationError::EmptyPath) => (),
+  other => panic!("{:?}", other)
+ }
+}
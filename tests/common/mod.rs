@@ -0,0 +1,16 @@
+//! The fixtures shared by the integration tests
+#![allow(dead_code)]
+
+use lonlat::prelude::*;
+
+/// The arguments are in the order of `LonLat::new`
+pub fn lonlat(lon: f64, lat: f64) -> LonLat
+{
+ LonLat::new(Angle::from_degrees(lon), Angle::from_degrees(lat))
+}
+
+/// The arguments are in the order of the strings; eg, "geo:43.062083,141.354389"
+pub fn latlon(lat: f64, lon: f64) -> LonLat
+{
+ lonlat(lon, lat)
+}
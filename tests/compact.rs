@@ -0,0 +1,84 @@
+use lonlat::prelude::*;
+
+mod common;
+use common::latlon;
+
+#[test]
+fn to_string_compact()
+{
+ let sapporo = latlon(43.062083, 141.354389);
+ let compact = |style, decimals, hemisphere| sapporo.to_string_compact(style, decimals, hemisphere).unwrap();
+
+ assert_eq!(compact(CoordinateStyle::DegreesDecimalMinutes, 0, HemisphereNotation::Suffix), "4304N14121E");
+ assert_eq!(compact(CoordinateStyle::DegreesMinutesSeconds, 0, HemisphereNotation::Suffix), "430343N1412116E");
+ assert_eq!(compact(CoordinateStyle::DegreesMinutesSeconds, 2, HemisphereNotation::Suffix), "430343.50N1412115.80E");
+ assert_eq!(compact(CoordinateStyle::DegreesDecimalMinutes, 3, HemisphereNotation::Prefix), "N4303.725E14121.263");
+ assert_eq!(compact(CoordinateStyle::DegreesMinutesSeconds, 0, HemisphereNotation::SignAlways), "+430343+1412116");
+
+ let south_west = latlon(-5.5, -7.25);
+ assert_eq!(
+  south_west.to_string_compact(CoordinateStyle::DegreesMinutesSeconds, 0, HemisphereNotation::Suffix).unwrap(),
+  "053000S0071500W"
+ );
+}
+
+#[test]
+fn from_compact_str()
+{
+ let parse = |source: &str| LonLat::from_compact_str(source).unwrap();
+ let assert_near = |source: &str, lat: f64, lon: f64| {
+  let parsed = parse(source);
+  assert!((parsed.lat.as_degrees() - lat).abs() < 1.0e-9, "{} {:?}", source, parsed);
+  assert!((parsed.lon.as_degrees() - lon).abs() < 1.0e-9, "{} {:?}", source, parsed);
+ };
+
+ assert_near("4303N14121E", 43.05, 141.35);
+ assert_near("430343N1412115E", 43.0 + 3.0 / 60.0 + 43.0 / 3600.0, 141.0 + 21.0 / 60.0 + 15.0 / 3600.0);
+ assert_near("430343.50N1412115.80E", 43.062083333, 141.354388889);
+ assert_near("N4303.725E14121.263", 43.062083333, 141.354383333);
+ assert_near("+430343.50+1412115.80", 43.062083333, 141.354388889);
+ assert_near("053000S0071500W", -5.5, -7.25);
+ assert_near("43N141E", 43.0, 141.0);
+ // The longitude first by the hemispheres, and the spaces between the axes
+ assert_near("14121E 4303N", 43.05, 141.35);
+
+ // round trip
+ let sapporo = latlon(43.062083, 141.354389);
+ let compact = sapporo.to_string_compact(CoordinateStyle::DegreesMinutesSeconds, 2, HemisphereNotation::Prefix).unwrap();
+ assert_near(&compact, 43.062083333, 141.354388889);
+}
+
+#[test]
+fn from_compact_str_errors()
+{
+ let parse_error = |source: &str| match LonLat::from_compact_str(source)
+ {
+  Err(CivilEngineeringLocationError::ParseError(e)) => e,
+  other => panic!("{:?}", other)
+ };
+
+ let e = parse_error("43034N1412115E");
+ assert_eq!(e.component, ParseComponent::Degrees);
+ assert_eq!(e.span, 0..5);
+
+ let e = parse_error("436043N1412115E");
+ assert_eq!(e.component, ParseComponent::Minutes);
+ assert_eq!(e.span, 2..4);
+
+ let e = parse_error("430343N1412160E");
+ assert_eq!(e.component, ParseComponent::Seconds);
+ assert_eq!(e.span, 12..14);
+
+ let e = parse_error("4303N4303N");
+ assert_eq!(e.component, ParseComponent::Hemisphere);
+ assert_eq!(e.span, 5..10);
+
+ let e = parse_error("9100N14121E");
+ assert_eq!(e.expected, "a latitude within ±90°");
+
+ let e = parse_error("4303N14121E/");
+ assert_eq!(e.component, ParseComponent::End);
+
+ let e = parse_error("4303N");
+ assert_eq!(e.span, 5..5);
+}
@@ -20,6 +20,18 @@ fn dms180()
  assert_abs_diff_ne!(sec, 15.8, epsilon = 1.0e-4);
 }
 
+#[test]
+fn normalize_negative_pi_positive_pi()
+{
+ let n = |degrees: f64| Angle::from_degrees(degrees).as_normalize_negative_pi_positive_pi().as_degrees();
+ assert_abs_diff_eq!(n(0.0), 0.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(n(190.0), -170.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(n(-190.0), 170.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(n(-358.0), 2.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(n(-540.0), -180.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(n(721.0), 1.0, epsilon = 1.0e-9);
+}
+
 macro_rules! parse_detail {
  ($source:expr, $expected:expr) => {{
   let angle = Angle::from_dms_str($source).unwrap();
@@ -67,6 +79,16 @@ fn from_dms_str_with_direction()
   Angle::from_dms_str_with_direction("42.826667E").unwrap(),
   (Angle::from_degrees(42.826667), AngleDirectionNotation::Longitude)
  );
+
+ assert_eq!(
+  Angle::from_dms_str_with_direction("-42.826667").unwrap(),
+  (Angle::from_degrees(-42.826667), AngleDirectionNotation::None)
+ );
+
+ assert_eq!(
+  Angle::from_dms_str_with_direction("42.826667S").unwrap(),
+  (Angle::from_degrees(-42.826667), AngleDirectionNotation::Latitude)
+ );
 }
 
 #[test]
@@ -86,6 +108,27 @@ fn from_dms_str_nwse()
  parse_detail!("43°3′43.5″S", (Sign::Negative, 43, 3, 43.5));
 }
 
+#[test]
+fn from_dms_str_nwse_prefix_and_words()
+{
+ parse_detail!("N 43°3′43.5″", (Sign::Positive, 43, 3, 43.5));
+ parse_detail!("W141°21′15.8″", (Sign::Negative, 141, 21, 15.8));
+ parse_detail!("43°3′43.5″ s", (Sign::Negative, 43, 3, 43.5));
+ parse_detail!("43 deg 3 min 43.5 sec W", (Sign::Negative, 43, 3, 43.5));
+ parse_detail!("43 degrees 3 minutes 43.5 seconds North", (Sign::Positive, 43, 3, 43.5));
+ parse_detail!("43d 3m 43.5s e", (Sign::Positive, 43, 3, 43.5));
+ parse_detail!("43d3m43.5s S", (Sign::Negative, 43, 3, 43.5));
+
+ let parse = |source: &str| Angle::from_dms_str_with_direction(source).unwrap();
+ assert_eq!(parse("S43.5"), (Angle::from_degrees(-43.5), AngleDirectionNotation::Latitude));
+ assert_eq!(parse("43.5 North"), (Angle::from_degrees(43.5), AngleDirectionNotation::Latitude));
+ assert_eq!(parse("west 141.5"), (Angle::from_degrees(-141.5), AngleDirectionNotation::Longitude));
+ assert_eq!(parse("43.5s"), (Angle::from_degrees(-43.5), AngleDirectionNotation::Latitude));
+ assert_eq!(parse("n 43 deg 30 min"), (Angle::from_degrees(43.5), AngleDirectionNotation::Latitude));
+ // Not a hemisphere in a word
+ assert_eq!(parse("lon 141.5"), (Angle::from_degrees(141.5), AngleDirectionNotation::None));
+}
+
 #[cfg(feature = "ja-JP")]
 #[test]
 fn from_dms_ja_jp()
@@ -105,3 +148,292 @@ fn from_dms_nwse_ja_jp()
  parse_detail!("北緯43度3分43.5秒", (Sign::Positive, 43, 3, 43.5));
  parse_detail!("南緯43度3分43.5秒", (Sign::Negative, 43, 3, 43.5));
 }
+
+#[cfg(feature = "ja-JP")]
+#[test]
+fn from_dms_kanji_overflow_ja_jp()
+{
+ let angle = "北緯一二三四五六七八九一二三四五六七八九一二三十度";
+ let source = format!("{}, 10", angle);
+ let source = source.as_str();
+ let options = ParseOptions::new().locales(&[&JaJp]);
+ assert!(matches!(LonLat::parse_with(source, &options), Err(CivilEngineeringLocationError::ParseError(_))));
+ assert!(matches!(Angle::from_dms_str(angle), Err(CivilEngineeringLocationError::ParseError(_))));
+ assert!(extract_lonlats_with(source, &options).is_empty());
+}
+
+#[cfg(feature = "ja-JP")]
+#[test]
+fn from_dms_full_width_ja_jp()
+{
+ parse_detail!("北緯４３度３分４３．５秒", (Sign::Positive, 43, 3, 43.5));
+ parse_detail!("西経１４１度２１分１５．８秒", (Sign::Negative, 141, 21, 15.8));
+ parse_detail!("－４３°３′４３．５″", (Sign::Negative, 43, 3, 43.5));
+ parse_detail!("４３°　３′　４３．５″　Ｓ", (Sign::Negative, 43, 3, 43.5));
+}
+
+#[cfg(feature = "ja-JP")]
+#[test]
+fn from_dms_kanji_numerals_ja_jp()
+{
+ parse_detail!("北緯四十三度三分四十三・五秒", (Sign::Positive, 43, 3, 43.5));
+ parse_detail!("東経百四十一度二十一分十五．八秒", (Sign::Positive, 141, 21, 15.8));
+ parse_detail!("南緯四三度〇三分四三・五秒", (Sign::Negative, 43, 3, 43.5));
+ parse_detail!("西経一四一度二一分一五・八秒", (Sign::Negative, 141, 21, 15.8));
+
+ let lonlat = "北緯四十三度三分四十三・五秒　東経百四十一度二十一分十五・八秒".parse::<LonLat>().unwrap();
+ assert_eq!(lonlat, "北緯43度3分43.5秒 東経141度21分15.8秒".parse::<LonLat>().unwrap());
+
+ // The spans of the errors are in the source as is
+ match Angle::from_dms_str("北緯四十三度三十").unwrap_err()
+ {
+  CivilEngineeringLocationError::ParseError(e) =>
+  {
+   assert_eq!(e.component, ParseComponent::Minutes);
+   assert_eq!(e.found, "三十");
+   assert_eq!(e.span, 18..24);
+  },
+  other => panic!("{:?}", other)
+ }
+}
+
+#[test]
+fn ddm()
+{
+ let angle = Angle::from_degrees(-141.354389);
+ let (sign, deg, min) = angle.as_ddm_180().unwrap();
+ assert_eq!(sign, Sign::Negative);
+ assert_eq!(deg, 141);
+ assert_abs_diff_eq!(min, 21.26334, epsilon = 1.0e-9);
+
+ let (sign, deg, min) = Angle::from_degrees(43.062083).as_ddm_90().unwrap();
+ assert_eq!(sign, Sign::Positive);
+ assert_eq!(deg, 43);
+ assert_abs_diff_eq!(min, 3.72498, epsilon = 1.0e-9);
+
+ let (deg, min) = Angle::from_degrees(-90.5).as_ddm_360().unwrap();
+ assert_eq!(deg, 269);
+ assert_abs_diff_eq!(min, 30.0, epsilon = 1.0e-9);
+
+ // The carry of the rounded minutes; never 60′
+ assert_eq!(Angle::from_degrees(10.0 - 1.0e-12).as_ddm_90().unwrap(), (Sign::Positive, 10, 0.0));
+ assert_eq!(Angle::from_degrees(10.0 - 1.0e-5).as_ddm_180_with_decimals(2).unwrap(), (Sign::Positive, 10, 0.0));
+
+ assert_eq!(angle.as_string_ddm_180().unwrap(), "-141°21.263’");
+ assert_eq!(angle.as_string_ddm_ew().unwrap(), "141°21.263’W");
+ assert_eq!(Angle::from_degrees(43.062083).as_string_ddm_90().unwrap(), "+43°3.725’");
+ assert_eq!(Angle::from_degrees(-43.062083).as_string_ddm_ns().unwrap(), "43°3.725’S");
+}
+
+#[test]
+fn from_ddm_str()
+{
+ let parse = |source: &str| Angle::from_dms_str_with_direction(source).unwrap();
+
+ let (angle, direction) = parse("43°03.725′N");
+ assert_abs_diff_eq!(angle.as_degrees(), 43.0 + 3.725 / 60.0, epsilon = 1.0e-12);
+ assert_eq!(direction, AngleDirectionNotation::Latitude);
+
+ let (angle, direction) = parse("141° 21.263’W");
+ assert_abs_diff_eq!(angle.as_degrees(), -(141.0 + 21.263 / 60.0), epsilon = 1.0e-12);
+ assert_eq!(direction, AngleDirectionNotation::Longitude);
+
+ let (angle, _) = parse("-43°3.725'");
+ assert_abs_diff_eq!(angle.as_degrees(), -(43.0 + 3.725 / 60.0), epsilon = 1.0e-12);
+
+ // The sign of the zero degrees
+ let (angle, _) = parse("0°30.5′S");
+ assert_abs_diff_eq!(angle.as_degrees(), -30.5 / 60.0, epsilon = 1.0e-12);
+
+ // The decimal minutes must be the last part in the strict mode; the lenient mode adds the seconds as before
+ assert!(Angle::from_dms_str_with("43°3.725′15″", &ParseOptions::strict()).is_err());
+ assert_abs_diff_eq!(Angle::from_dms_str("43°3.5′15″").unwrap().as_degrees(), 43.0 + 3.5 / 60.0 + 15.0 / 3600.0, epsilon = 1.0e-12);
+}
+
+#[cfg(feature = "ja-JP")]
+#[test]
+fn ddm_ja_jp()
+{
+ let angle = Angle::from_degrees(141.354389);
+ let formatter = CoordinateFormatter::new().style(CoordinateStyle::DegreesDecimalMinutes).decimals(ANGLE_DDM_DECIMALS).locale(&JaJp);
+ assert_eq!(formatter.clone().hemisphere(HemisphereNotation::SignAlways).format_lon(angle).unwrap(), "+141度21.263分");
+ assert_eq!(formatter.format_lon(angle).unwrap(), "東経141度21.263分");
+ assert_eq!(formatter.format_lat(Angle::from_degrees(-43.062083)).unwrap(), "南緯43度3.725分");
+
+ let (angle, direction) = Angle::from_dms_str_with_direction("北緯43度3.725分").unwrap();
+ assert_abs_diff_eq!(angle.as_degrees(), 43.0 + 3.725 / 60.0, epsilon = 1.0e-12);
+ assert_eq!(direction, AngleDirectionNotation::Latitude);
+}
+
+/// Deterministic pseudo-random angles [deg] including the values just below the carries
+fn sample_degrees() -> Vec<f64>
+{
+ let mut state = 0x2545_f491_4f6c_dd1du64;
+ let mut samples: Vec<f64> = (0..20_000)
+  .map(|_| {
+   state ^= state << 13;
+   state ^= state >> 7;
+   state ^= state << 17;
+   (state >> 11) as f64 / (1u64 << 53) as f64 * 1080.0 - 540.0
+  })
+  .collect();
+ for &degrees in &[0.0, 1.0, 59.0, 89.0, 90.0, 179.0, 180.0, 359.0, 360.0]
+ {
+  for &epsilon in &[1.0e-12, 1.0e-9, 1.0e-6, 0.04 / 3600.0, 0.4 / 3600.0]
+  {
+   samples.push(degrees - epsilon);
+   samples.push(-degrees + epsilon);
+  }
+ }
+ samples
+}
+
+#[test]
+fn dms_decomposition_is_canonical()
+{
+ for degrees in sample_degrees()
+ {
+  let angle = Angle::from_degrees(degrees);
+  for decimals in 0..=ANGLE_MAXIMUM_DECIMALS
+  {
+   let half_unit = 0.5 * 10f64.powi(-(decimals as i32)) / 3600.0;
+   let check = |sign: Sign, deg: f64, min: u8, sec: f64, expected: f64| {
+    assert!(min < 60, "{} {} -> {} {} {}", degrees, decimals, deg, min, sec);
+    assert!(sec < 60.0, "{} {} -> {} {} {}", degrees, decimals, deg, min, sec);
+    let total = sign.as_f64() * (deg + min as f64 / 60.0 + sec / 3600.0);
+    let difference = Angle::from_degrees(total - expected).as_normalize_negative_pi_positive_pi().as_degrees();
+    assert!(difference.abs() <= half_unit + 1.0e-11, "{} {} -> {}", degrees, decimals, total);
+    // The formatted seconds never carry out
+    assert!(format!("{:.*}", decimals, sec).parse::<f64>().unwrap() < 60.0);
+   };
+
+   let (deg, min, sec) = angle.as_dms_360_with_decimals(decimals).unwrap();
+   assert!(deg < 360);
+   check(Sign::Positive, deg as f64, min, sec, degrees);
+
+   let (sign, deg, min, sec) = angle.as_dms_180_with_decimals(decimals).unwrap();
+   assert!(deg < 180 || (deg == 180 && sign == Sign::Negative && min == 0 && sec == 0.0));
+   check(sign, deg as f64, min, sec, degrees);
+
+   let (sign, deg, min, sec) = angle.as_dms_90_with_decimals(decimals).unwrap();
+   assert!(deg < 90 || (deg == 90 && min == 0 && sec == 0.0));
+   let latitude = angle.as_normalize_negative_half_pi_positive_half_pi().as_degrees();
+   check(sign, deg as f64, min, sec, latitude);
+
+   // DDM; the minutes are rounded at the decimal places
+   let check_ddm = |sign: Sign, deg: f64, min: f64, expected: f64| {
+    assert!(format!("{:.*}", decimals, min).parse::<f64>().unwrap() < 60.0, "{} {} -> {} {}", degrees, decimals, deg, min);
+    let total = sign.as_f64() * (deg + min / 60.0);
+    let difference = Angle::from_degrees(total - expected).as_normalize_negative_pi_positive_pi().as_degrees();
+    assert!(difference.abs() <= half_unit * 60.0 + 1.0e-11, "{} {} -> {}", degrees, decimals, total);
+   };
+   let (deg, min) = angle.as_ddm_360_with_decimals(decimals).unwrap();
+   assert!(deg < 360);
+   check_ddm(Sign::Positive, deg as f64, min, degrees);
+   let (sign, deg, min) = angle.as_ddm_180_with_decimals(decimals).unwrap();
+   check_ddm(sign, deg as f64, min, degrees);
+   let (sign, deg, min) = angle.as_ddm_90_with_decimals(decimals).unwrap();
+   check_ddm(sign, deg as f64, min, latitude);
+  }
+ }
+
+ for decimals in [ANGLE_MAXIMUM_DECIMALS + 1, 14, 20]
+ {
+  let angle = Angle::from_degrees(359.9);
+  assert!(matches!(angle.as_dms_360_with_decimals(decimals), Err(CivilEngineeringLocationError::DecimalsOutOfRange(_))));
+  assert!(matches!(angle.as_dms_180_with_decimals(decimals), Err(CivilEngineeringLocationError::DecimalsOutOfRange(_))));
+  assert!(matches!(angle.as_dms_90_with_decimals(decimals), Err(CivilEngineeringLocationError::DecimalsOutOfRange(_))));
+  assert!(matches!(angle.as_ddm_360_with_decimals(decimals), Err(CivilEngineeringLocationError::DecimalsOutOfRange(_))));
+ }
+}
+
+#[test]
+fn dms_decompositions_agree()
+{
+ for degrees in sample_degrees().into_iter().map(|d| d.rem_euclid(90.0))
+ {
+  let angle = Angle::from_degrees(degrees);
+  let (deg, min, sec) = angle.as_dms_360().unwrap();
+  let (_, deg180, min180, sec180) = angle.as_dms_180().unwrap();
+  let (_, deg90, min90, sec90) = angle.as_dms_90().unwrap();
+  if deg == 90
+  {
+   continue;
+  }
+  assert_eq!((deg as u8, min, sec), (deg180, min180, sec180));
+  assert_eq!((deg as u8, min, sec), (deg90, min90, sec90));
+ }
+}
+
+#[test]
+fn dms_strings_are_canonical()
+{
+ let angle = Angle::from_degrees(43.0 + 59.0 / 60.0 + 59.97 / 3600.0);
+ assert_eq!(angle.as_string_dms_90().unwrap(), "+44°0’0.0”");
+ assert_eq!(angle.as_string_dms_ns().unwrap(), "44°0’0.0”N");
+ assert_eq!(angle.as_string_dms_360().unwrap(), "+44°0’0.0”");
+ assert_eq!(Angle::from_degrees(-0.01 / 3600.0).as_string_dms_360().unwrap(), "+0°0’0.0”");
+ assert_eq!(Angle::from_degrees(179.99999).as_string_dms_180().unwrap(), "-180°0’0.0”");
+
+ let (sign, deg, min, sec) = Angle::from_degrees(-(12.0 + 59.0 / 60.0 + 59.6 / 3600.0)).as_dms_180_with_decimals(0).unwrap();
+ assert_eq!((sign, deg, min, sec), (Sign::Negative, 13, 0, 0.0));
+}
+
+#[test]
+fn angular_units()
+{
+ let right = Angle::from_degrees(90.0);
+ assert_abs_diff_eq!(right.as_gradians(), 100.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(right.as_mils(), 1600.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(right.as_turns(), 0.25, epsilon = 1.0e-12);
+ assert_abs_diff_eq!(right.as_hours(), 6.0, epsilon = 1.0e-12);
+ assert_abs_diff_eq!(Angle::from_gradians(50.0).as_degrees(), 45.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(Angle::from_mils(3200.0).as_degrees(), 180.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(Angle::from_turns(-0.5).as_degrees(), -180.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(Angle::from_hours(1.0).as_degrees(), 15.0, epsilon = 1.0e-9);
+
+ assert_eq!(right.as_string_gradians(), "100 gon");
+ assert_eq!(Angle::from_degrees(180.0).as_string_mils(), "3200 mil");
+ assert_eq!(Angle::from_degrees(180.0).as_string_turns(), "0.5 tr");
+
+ // The right ascension of Sirius; 6h45m8.92s = 101.287167°
+ let sirius = Angle::from_degrees(101.287167);
+ assert_eq!(sirius.as_string_hms().unwrap(), "6h45m8.92s");
+ assert_eq!(Angle::from_degrees(-15.0).as_hms_with_decimals(0).unwrap(), (23, 0, 0.0));
+ assert_eq!(sirius.as_hms_with_decimals(ANGLE_MAXIMUM_DECIMALS).unwrap().0, 6);
+ for decimals in [ANGLE_MAXIMUM_DECIMALS + 1, 15, 20]
+ {
+  assert!(matches!(sirius.as_hms_with_decimals(decimals), Err(CivilEngineeringLocationError::DecimalsOutOfRange(_))));
+ }
+ assert!(Angle::from_degrees(f64::NAN).as_string_hms().is_err());
+}
+
+#[test]
+fn from_unit_str()
+{
+ let degrees = |source: &str| Angle::from_unit_str(source).unwrap().as_degrees();
+ assert_abs_diff_eq!(degrees("100 gon"), 90.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(degrees("50grad"), 45.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(degrees("100ᵍ"), 90.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(degrees(" -1600 MILS "), -90.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(degrees("0.25 turn"), 90.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(degrees("0.5 tr"), 180.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(degrees("6h45m8.92s"), 101.287167, epsilon = 1.0e-6);
+ assert_abs_diff_eq!(degrees("6h 45m 8.92s"), 101.287167, epsilon = 1.0e-6);
+ assert_abs_diff_eq!(degrees("6ʰ45ᵐ8.92ˢ"), 101.287167, epsilon = 1.0e-6);
+ assert_abs_diff_eq!(degrees("6.5 hours"), 97.5, epsilon = 1.0e-9);
+ assert_eq!(Angle::from_unit_str_with_unit("1600 mil").unwrap().1, AngularUnit::Mils);
+
+ let parse_error = |source: &str| match Angle::from_unit_str(source)
+ {
+  Err(CivilEngineeringLocationError::ParseError(e)) => e,
+  other => panic!("{:?}", other)
+ };
+ let e = parse_error("100 gal");
+ assert_eq!(e.component, ParseComponent::Unit);
+ assert_eq!(e.span, 4..7);
+ assert_eq!(parse_error("gon").component, ParseComponent::Value);
+ assert_eq!(parse_error("6h75m").component, ParseComponent::Minutes);
+ assert_eq!(parse_error("6h45m8.92").component, ParseComponent::Seconds);
+ assert_eq!(parse_error("100 gon 5").component, ParseComponent::End);
+}
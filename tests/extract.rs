@@ -0,0 +1,52 @@
+use lonlat::prelude::*;
+
+mod common;
+use common::latlon;
+
+fn spans(text: &str) -> Vec<&str>
+{
+ extract_lonlats(text).into_iter().map(|extracted| &text[extracted.span]).collect()
+}
+
+#[test]
+fn extract_lonlats_from_text()
+{
+ let text = "The pole fell at 43.06, 141.35 this morning; see geo:35.68,139.77 too.\nAlso 43°3′43.5″N 141°21′15.8″E (Sapporo).";
+ let extracted = extract_lonlats(text);
+ assert_eq!(extracted.len(), 3);
+ assert_eq!(extracted[0].lonlat, latlon(43.06, 141.35));
+ assert_eq!(extracted[0].span, 17..30);
+ assert_eq!(extracted[1].lonlat, latlon(35.68, 139.77));
+ assert_eq!(&text[extracted[1].span.clone()], "geo:35.68,139.77");
+ assert_eq!(extracted[2].lonlat, "43°3′43.5″N 141°21′15.8″E".parse::<LonLat>().unwrap());
+ assert_eq!(&text[extracted[2].span.clone()], "43°3′43.5″N 141°21′15.8″E");
+
+ assert_eq!(spans("N 43.06 E 141.35, then S33.86 E151.21."), vec!["N 43.06 E 141.35", "S33.86 E151.21"]);
+ assert_eq!(spans("141.35E 43.06N"), vec!["141.35E 43.06N"]);
+}
+
+#[test]
+fn extract_lonlats_rejects_non_coordinates()
+{
+ assert!(spans("Meet at 10 15 in room 3").is_empty());
+ assert!(spans("version v1.2, 3.4 and 2024-10-18").is_empty());
+ assert!(spans("43°75′N 141°E").is_empty());
+ assert!(spans("95.0, 141.35").is_empty());
+ assert!(spans("").is_empty());
+
+ let auto = ParseOptions::default().order(ParseAxisOrder::Auto);
+ let extracted = extract_lonlats_with("GeoJSON: [141.35, 43.06]", &auto);
+ assert_eq!(extracted.len(), 1);
+ assert_eq!(extracted[0].lonlat, latlon(43.06, 141.35));
+}
+
+#[cfg(feature = "ja-JP")]
+#[test]
+fn extract_lonlats_ja_jp()
+{
+ let text = "現場は北緯４３度３分４３．５秒 東経１４１度２１分１５．８秒です。";
+ let extracted = extract_lonlats(text);
+ assert_eq!(extracted.len(), 1);
+ assert_eq!(&text[extracted[0].span.clone()], "北緯４３度３分４３．５秒 東経１４１度２１分１５．８秒");
+ assert_eq!(extracted[0].lonlat, "43°3′43.5″N 141°21′15.8″E".parse::<LonLat>().unwrap());
+}
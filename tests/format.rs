@@ -0,0 +1,123 @@
+use lonlat::prelude::*;
+
+mod common;
+use common::lonlat;
+
+#[test]
+fn default()
+{
+ let sapporo = lonlat(141.354389, 43.062083);
+ assert_eq!(CoordinateFormatter::new().format(&sapporo).unwrap(), "+43°3’43.5” +141°21’15.8”");
+ assert_eq!(sapporo.to_string_dms_comma().unwrap(), "+43°3’43.5”,+141°21’15.8”");
+}
+
+#[test]
+fn styles()
+{
+ let target = lonlat(-70.6483, -33.4569);
+ let formatter = CoordinateFormatter::new().hemisphere(HemisphereNotation::Suffix).separator(", ");
+
+ let dd = formatter.clone().style(CoordinateStyle::DecimalDegrees).decimals(4);
+ assert_eq!(dd.format(&target).unwrap(), "33.4569°S, 70.6483°W");
+
+ let ddm = formatter.clone().style(CoordinateStyle::DegreesDecimalMinutes).decimals(3);
+ assert_eq!(ddm.format(&target).unwrap(), "33°27.414’S, 70°38.898’W");
+
+ let dms = formatter.style(CoordinateStyle::DegreesMinutesSeconds).decimals(2);
+ assert_eq!(dms.format(&target).unwrap(), "33°27’24.84”S, 70°38’53.88”W");
+}
+
+#[test]
+fn symbols_padding_and_order()
+{
+ let target = lonlat(8.5, 7.25);
+ let formatter = CoordinateFormatter::new()
+  .decimals(0)
+  .zero_padding(true)
+  .hemisphere(HemisphereNotation::Prefix)
+  .order(AxisOrder::LonLat);
+
+ assert_eq!(
+  formatter.clone().symbols(CoordinateSymbols::Ascii).format(&target).unwrap(),
+  r#"E008°30'00" N07°15'00""#
+ );
+ assert_eq!(formatter.clone().symbols(CoordinateSymbols::Prime).format(&target).unwrap(), "E008°30′00″ N07°15′00″");
+ assert_eq!(formatter.symbols(CoordinateSymbols::Typographic).format(&target).unwrap(), "E008°30’00” N07°15’00”");
+
+ let dd = CoordinateFormatter::new()
+  .style(CoordinateStyle::DecimalDegrees)
+  .decimals(2)
+  .zero_padding(true)
+  .symbols(CoordinateSymbols::None)
+  .hemisphere(HemisphereNotation::Sign)
+  .separator(",");
+ assert_eq!(dd.format(&lonlat(-8.5, 7.25)).unwrap(), "07.25,-008.50");
+}
+
+#[test]
+fn rounding()
+{
+ // 59.96” rounds up to the next minute and the next degree
+ let formatter = CoordinateFormatter::new().hemisphere(HemisphereNotation::Suffix);
+ let angle = Angle::from_degrees(10.0 - 0.04 / 3600.0);
+ assert_eq!(formatter.format_lat(angle).unwrap(), "10°0’0.0”N");
+
+ let ddm = formatter.clone().style(CoordinateStyle::DegreesDecimalMinutes).decimals(2);
+ assert_eq!(ddm.format_lon(Angle::from_degrees(-(20.0 - 0.001 / 60.0))).unwrap(), "20°0.00’W");
+
+ // A negative value rounded to zero has no sign
+ let sign = CoordinateFormatter::new().hemisphere(HemisphereNotation::SignAlways);
+ assert_eq!(sign.format_lon(Angle::from_degrees(-1.0e-9)).unwrap(), "+0°0’0.0”");
+
+ let trimmed = CoordinateFormatter::new().style(CoordinateStyle::DecimalDegrees).decimals(6).trim_zeros(true);
+ assert_eq!(trimmed.format_lat(Angle::from_degrees(0.1 + 0.2)).unwrap(), "+0.3°");
+ assert_eq!(trimmed.format_lat(Angle::from_degrees(45.0)).unwrap(), "+45°");
+}
+
+#[test]
+fn errors()
+{
+ let formatter = CoordinateFormatter::new();
+ match formatter.format_lat(Angle::from_degrees(f64::NAN))
+ {
+  Err(CivilEngineeringLocationError::Nan) => (),
+  other => panic!("{:?}", other)
+ }
+ match formatter.format_lon(Angle::from_degrees(f64::INFINITY))
+ {
+  Err(CivilEngineeringLocationError::Infinite) => (),
+  other => panic!("{:?}", other)
+ }
+
+ let target = lonlat(141.354389, 43.062083);
+ for style in [CoordinateStyle::DecimalDegrees, CoordinateStyle::DegreesDecimalMinutes, CoordinateStyle::DegreesMinutesSeconds]
+ {
+  assert!(CoordinateFormatter::new().style(style).decimals(ANGLE_MAXIMUM_DECIMALS).format(&target).is_ok());
+  match CoordinateFormatter::new().style(style).decimals(25).format(&target)
+  {
+   Err(CivilEngineeringLocationError::DecimalsOutOfRange(25)) => (),
+   other => panic!("{:?}", other)
+  }
+ }
+}
+
+#[test]
+fn geo_uri()
+{
+ let target = lonlat(140.0 + 0.811389, 0.1 + 0.2);
+ assert_eq!(target.to_string_geo_uri().unwrap(), "geo:0.3,140.811389");
+
+ // [-180..180); +180° is the same meridian as -180°
+ assert_eq!(lonlat(180.0, 0.0).to_string_geo_uri().unwrap(), "geo:0,-180");
+ assert_eq!(lonlat(-180.0, 0.0).to_string_geo_uri().unwrap(), "geo:0,-180");
+}
+
+#[test]
+fn ja_jp()
+{
+ let formatter = CoordinateFormatter::new()
+  .style(CoordinateStyle::DegreesDecimalMinutes)
+  .decimals(2)
+  .locale(&JaJp);
+ assert_eq!(formatter.format(&lonlat(141.354389, 43.062083)).unwrap(), "北緯43度3.72分 東経141度21.26分");
+}
@@ -0,0 +1,63 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+// Vincenty(1975); Flinders Peak -> Buninyong on GRS80
+fn flinders_peak() -> LonLat
+{
+ LonLat::new(Angle::from_dms(144.0, 25.0, 29.52440).unwrap(), Angle::from_dms(-37.0, 57.0, 3.72030).unwrap())
+}
+
+fn buninyong() -> LonLat
+{
+ LonLat::new(Angle::from_dms(143.0, 55.0, 35.38390).unwrap(), Angle::from_dms(-37.0, 39.0, 10.15610).unwrap())
+}
+
+#[test]
+fn geodesic_inverse()
+{
+ let actual = flinders_peak().geodesic_inverse(&buninyong(), &Ellipsoid::GRS80).unwrap();
+ assert_abs_diff_eq!(actual.distance.as_meters(), 54_972.271, epsilon = 1.0e-3);
+ assert_abs_diff_eq!(
+  actual.initial_azimuth.as_normalize_0_pi().as_degrees(),
+  Angle::from_dms(306.0, 52.0, 5.37).unwrap().as_degrees(),
+  epsilon = 1.0e-5
+ );
+ assert_abs_diff_eq!(
+  actual.final_azimuth.as_normalize_0_pi().as_degrees(),
+  Angle::from_dms(307.0, 10.0, 25.07).unwrap().as_degrees(),
+  epsilon = 1.0e-5
+ );
+
+ let zero = flinders_peak().geodesic_distance(&flinders_peak(), &Ellipsoid::GRS80).unwrap();
+ assert_abs_diff_eq!(zero.as_meters(), 0.0);
+
+ assert!(lonlat(0.0, 0.0).geodesic_inverse(&lonlat(179.7, 0.5), &Ellipsoid::WGS84).is_err());
+}
+
+#[test]
+fn geodesic_direct()
+{
+ let azimuth = Angle::from_dms(306.0, 52.0, 5.37).unwrap();
+ let actual = flinders_peak().geodesic_direct(azimuth, Length::from_meters(54_972.271), &Ellipsoid::GRS80);
+ assert_abs_diff_eq!(actual.destination.lon.as_degrees(), buninyong().lon.as_degrees(), epsilon = 1.0e-7);
+ assert_abs_diff_eq!(actual.destination.lat.as_degrees(), buninyong().lat.as_degrees(), epsilon = 1.0e-7);
+}
+
+#[test]
+fn great_circle()
+{
+ let radius = Length::from_meters(6_371_000.0);
+ let a = lonlat(0.0, 0.0);
+ let b = lonlat(90.0, 0.0);
+ assert_abs_diff_eq!(a.great_circle_distance(&b, radius).as_meters(), radius.as_meters() * PI_DIV_2, epsilon = 1.0e-6);
+ assert_abs_diff_eq!(a.great_circle_initial_bearing(&b).as_degrees(), 90.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(a.great_circle_initial_bearing(&lonlat(0.0, 10.0)).as_degrees(), 0.0, epsilon = 1.0e-9);
+
+ let c = a.great_circle_destination(Angle::from_degrees(90.0), Length::from_meters(radius.as_meters() * PI_DIV_2), radius);
+ assert_abs_diff_eq!(c.lon.as_degrees(), 90.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(c.lat.as_degrees(), 0.0, epsilon = 1.0e-9);
+}
@@ -0,0 +1,91 @@
+use lonlat::prelude::*;
+
+mod common;
+use common::lonlat;
+
+// A 2° grid of 180 × 90 points over the whole globe(from -179.95° and -89.5°; shifted from the antimeridian and the poles)
+fn grid() -> Vec<LonLat>
+{
+ let mut grid = Vec::new();
+ for i in 0..180
+ {
+  for j in 0..90
+  {
+   grid.push(lonlat(-179.95 + i as f64 * 2.0, -89.5 + j as f64 * 2.0));
+  }
+ }
+ grid
+}
+
+fn brute_force_within(points: &[LonLat], target: &LonLat, radius: f64) -> usize
+{
+ points
+  .iter()
+  .filter(|p| target.geodesic_distance(*p, &Ellipsoid::WGS84).is_ok_and(|d| d.as_meters() <= radius))
+  .count()
+}
+
+#[test]
+fn within_radius()
+{
+ let points = grid();
+ let index = LonLatIndex::new(points.clone(), Ellipsoid::WGS84);
+ assert_eq!(index.len(), points.len());
+
+ for target in &[lonlat(0.0, 0.0), lonlat(179.9, 30.0), lonlat(12.3, 89.9), lonlat(-45.0, -60.0)]
+ {
+  let radius = 500_000.0;
+  let actual = index.within_radius(target, Length::from_meters(radius));
+  assert_eq!(actual.len(), brute_force_within(&points, target, radius));
+  assert!(actual.windows(2).all(|w| w[0].1.as_meters() <= w[1].1.as_meters()));
+  assert!(actual.iter().all(|(_, d)| d.as_meters() <= radius));
+ }
+}
+
+#[test]
+fn nearest()
+{
+ let points = grid();
+ let index: LonLatIndex<LonLat> = points.iter().cloned().collect();
+
+ let target = lonlat(179.99, 0.6);
+ let actual = index.nearest(&target, 3);
+ assert_eq!(actual.len(), 3);
+
+ let mut expected: Vec<f64> = points
+  .iter()
+  .filter_map(|p| target.geodesic_distance(p, &Ellipsoid::WGS84).ok())
+  .map(|d| d.as_meters())
+  .collect();
+ expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+ for (a, e) in actual.iter().zip(expected.iter())
+ {
+  assert_eq!(a.1.as_meters(), *e);
+ }
+
+ assert!(index.nearest(&target, 0).is_empty());
+ assert_eq!(index.nearest(&target, points.len() + 10).len(), points.len());
+}
+
+#[test]
+fn within_bounds()
+{
+ let points = grid();
+ let index = LonLatIndex::new(points.clone(), Ellipsoid::WGS84);
+
+ for bounds in &[
+  LonLatBounds::new(Angle::from_degrees(170.0), Angle::from_degrees(-10.0), Angle::from_degrees(-170.0), Angle::from_degrees(10.0)),
+  LonLatBounds::new(Angle::from_degrees(-10.0), Angle::from_degrees(60.0), Angle::from_degrees(100.0), Angle::from_degrees(90.0)),
+  LonLatBounds::new(Angle::from_degrees(-180.0), Angle::from_degrees(-90.0), Angle::from_degrees(180.0), Angle::from_degrees(-80.0))
+ ]
+ {
+  let expected = points.iter().filter(|p| bounds.contains(*p)).count();
+  assert!(expected > 0);
+  assert_eq!(index.within_bounds(bounds).len(), expected);
+ }
+
+ let empty: LonLatIndex<LonLat> = LonLatIndex::new(vec![], Ellipsoid::WGS84);
+ assert!(empty.is_empty());
+ assert!(empty.within_radius(&lonlat(0.0, 0.0), Length::from_meters(1.0)).is_empty());
+ assert!(empty.nearest(&lonlat(0.0, 0.0), 1).is_empty());
+}
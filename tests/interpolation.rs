@@ -0,0 +1,88 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+#[test]
+fn geodesic_midpoint()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+
+ let actual = lonlat(0.0, 0.0).geodesic_midpoint(&lonlat(90.0, 0.0), &ellipsoid).unwrap();
+ assert_abs_diff_eq!(actual.lon.as_degrees(), 45.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(actual.lat.as_degrees(), 0.0, epsilon = 1.0e-9);
+
+ // Tokyo -> San Francisco; across the antimeridian
+ let (a, b) = (lonlat(139.767, 35.681), lonlat(-122.419, 37.775));
+ let m = a.geodesic_midpoint(&b, &ellipsoid).unwrap();
+ let d1 = a.geodesic_distance(&m, &ellipsoid).unwrap().as_meters();
+ let d2 = m.geodesic_distance(&b, &ellipsoid).unwrap().as_meters();
+ assert_abs_diff_eq!(d1, d2, epsilon = 1.0e-3);
+ assert!(m.lat.as_degrees() > 45.0);
+}
+
+#[test]
+fn geodesic_densify()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let (a, b) = (lonlat(139.767, 35.681), lonlat(-122.419, 37.775));
+ let points = a.geodesic_densify(&b, 8, &ellipsoid).unwrap();
+ assert_eq!(points.len(), 9);
+ assert_eq!(points[0], a);
+ assert_eq!(points[8], b);
+
+ let total = a.geodesic_distance(&b, &ellipsoid).unwrap().as_meters();
+ for pair in points.windows(2)
+ {
+  assert_abs_diff_eq!(pair[0].geodesic_distance(&pair[1], &ellipsoid).unwrap().as_meters(), total / 8.0, epsilon = 1.0e-3);
+ }
+
+ let quarter = a.geodesic_intermediate(&b, 0.25, &ellipsoid).unwrap();
+ assert_abs_diff_eq!(quarter.lon.as_degrees(), points[2].lon.as_degrees(), epsilon = 1.0e-12);
+ assert_abs_diff_eq!(quarter.lat.as_degrees(), points[2].lat.as_degrees(), epsilon = 1.0e-12);
+}
+
+#[test]
+fn great_circle()
+{
+ let m = lonlat(0.0, 0.0).great_circle_midpoint(&lonlat(0.0, 90.0)).unwrap();
+ assert_abs_diff_eq!(m.lat.as_degrees(), 45.0, epsilon = 1.0e-9);
+
+ let m = lonlat(170.0, 0.0).great_circle_midpoint(&lonlat(-170.0, 0.0)).unwrap();
+ assert_abs_diff_eq!(m.lon.as_degrees().abs(), 180.0, epsilon = 1.0e-9);
+
+ let points = lonlat(0.0, 0.0).great_circle_densify(&lonlat(90.0, 0.0), 3).unwrap();
+ assert_eq!(points.len(), 4);
+ assert_abs_diff_eq!(points[1].lon.as_degrees(), 30.0, epsilon = 1.0e-9);
+}
+
+#[test]
+fn great_circle_antipodal()
+{
+ let antipodal = |source: LonLat, target: LonLat| {
+  matches!(source.great_circle_midpoint(&target), Err(CivilEngineeringLocationError::GreatCircleAntipodal))
+   && matches!(source.great_circle_densify(&target, 4), Err(CivilEngineeringLocationError::GreatCircleAntipodal))
+ };
+ assert!(antipodal(lonlat(0.0, 0.0), lonlat(180.0, 0.0)));
+ assert!(antipodal(lonlat(141.0, 43.0), lonlat(-39.0, -43.0)));
+ assert!(antipodal(lonlat(0.0, 90.0), lonlat(0.0, -90.0)));
+ // Nearly antipodal but defined
+ assert!(lonlat(0.0, 0.0).great_circle_midpoint(&lonlat(179.0, 0.0)).is_ok());
+}
+
+#[test]
+fn with_alt()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let a = LonLatAlt::new(Angle::from_degrees(141.0), Angle::from_degrees(43.0), Length::from_meters(100.0));
+ let b = LonLatAlt::new(Angle::from_degrees(142.0), Angle::from_degrees(44.0), Length::from_meters(500.0));
+
+ let m = a.geodesic_midpoint_with_alt(&b, &ellipsoid).unwrap();
+ assert_abs_diff_eq!(m.alt.as_meters(), 300.0, epsilon = 1.0e-9);
+
+ let points = a.geodesic_densify_with_alt(&b, 4, &ellipsoid).unwrap();
+ let alts: Vec<f64> = points.iter().map(|p| p.alt.as_meters()).collect();
+ assert_eq!(alts, vec![100.0, 200.0, 300.0, 400.0, 500.0]);
+}
@@ -0,0 +1,73 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+#[test]
+fn great_circle_intersections()
+{
+ // The equator and the meridian of 10°E
+ let a = GeodesicLine::from_bearing(&lonlat(0.0, 0.0), Angle::from_degrees(90.0));
+ let b = GeodesicLine::from_points(&lonlat(10.0, -5.0), &lonlat(10.0, 5.0));
+ let [near, far] = a.great_circle_intersections(&b).unwrap();
+ assert_abs_diff_eq!(near.lon.as_degrees(), 10.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(near.lat.as_degrees(), 0.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(far.lon.as_degrees(), -170.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(far.lat.as_degrees(), 0.0, epsilon = 1.0e-9);
+}
+
+#[test]
+fn geodesic_intersections()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let a = GeodesicLine::from_bearing(&lonlat(139.0, 35.0), Angle::from_degrees(60.0));
+ let b = GeodesicLine::from_bearing(&lonlat(141.0, 35.0), Angle::from_degrees(-30.0));
+ let [near, _] = a.geodesic_intersections(&b, &ellipsoid).unwrap();
+
+ // The intersection is on the both geodesics
+ for (origin, bearing) in &[(lonlat(139.0, 35.0), 60.0), (lonlat(141.0, 35.0), -30.0)]
+ {
+  let azimuth = origin.geodesic_inverse(&near, &ellipsoid).unwrap().initial_azimuth;
+  assert_abs_diff_eq!(
+   azimuth.as_normalize_negative_pi_positive_pi().as_degrees(),
+   *bearing,
+   epsilon = 1.0e-8
+  );
+ }
+}
+
+#[test]
+fn geodesic_segment_intersection()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let a = GeodesicLine::from_points(&lonlat(179.0, -1.0), &lonlat(-179.0, 1.0));
+ let b = GeodesicLine::from_points(&lonlat(179.0, 1.0), &lonlat(-179.0, -1.0));
+ let actual = a.geodesic_segment_intersection(&b, &ellipsoid).unwrap().unwrap();
+ assert_abs_diff_eq!(actual.lon.as_degrees().abs(), 180.0, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(actual.lat.as_degrees(), 0.0, epsilon = 1.0e-9);
+
+ let c = GeodesicLine::from_points(&lonlat(0.0, 0.0), &lonlat(1.0, 1.0));
+ let d = GeodesicLine::from_points(&lonlat(2.0, 0.0), &lonlat(3.0, 1.0));
+ assert_eq!(c.geodesic_segment_intersection(&d, &ellipsoid).unwrap(), None);
+}
+
+#[test]
+fn errors()
+{
+ let a = GeodesicLine::from_points(&lonlat(0.0, 0.0), &lonlat(10.0, 0.0));
+ let b = GeodesicLine::from_bearing(&lonlat(20.0, 0.0), Angle::from_degrees(-90.0));
+ match a.great_circle_intersections(&b)
+ {
+  Err(CivilEngineeringLocationError::IntersectionCoincident) => (),
+  other => panic!("{:?}", other)
+ }
+
+ let c = GeodesicLine::from_points(&lonlat(0.0, 0.0), &lonlat(0.0, 0.0));
+ match a.great_circle_intersections(&c)
+ {
+  Err(CivilEngineeringLocationError::IntersectionUndefinedLine) => (),
+  other => panic!("{:?}", other)
+ }
+}
@@ -0,0 +1,94 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+fn sapporo() -> LonLat
+{
+ LonLat::new(Angle::from_degrees(141.354389), Angle::from_degrees(43.062083))
+}
+
+#[test]
+fn format_with_locales()
+{
+ let format = |locale: &'static dyn Locale, lonlat: &LonLat| CoordinateFormatter::new().locale(locale).format(lonlat).unwrap();
+
+ assert_eq!(format(&En, &sapporo()), "43°3′43.5″N 141°21′15.8″E");
+ assert_eq!(format(&JaJp, &sapporo()), "北緯43度3分43.5秒 東経141度21分15.8秒");
+ assert_eq!(format(&ZhCn, &sapporo()), "北纬43度3分43.5秒 东经141度21分15.8秒");
+ assert_eq!(format(&ZhTw, &sapporo()), "北緯43度3分43.5秒 東經141度21分15.8秒");
+ assert_eq!(format(&KoKr, &sapporo()), "북위43도3분43.5초 동경141도21분15.8초");
+ assert_eq!(format(&Fr, &sapporo()), "43°3′43,5″N 141°21′15,8″E");
+ assert_eq!(format(&De, &sapporo()), "43°3′43,5″N 141°21′15,8″O");
+
+ let west = LonLat::new(Angle::from_degrees(-3.7038), Angle::from_degrees(40.4168));
+ assert_eq!(format(&Es, &west), "40°25′0,5″N 3°42′13,7″O");
+ assert_eq!(format(&De, &west), "40°25′0,5″N 3°42′13,7″W");
+}
+
+#[test]
+fn parse_with_locales()
+{
+ let options = ParseOptions::new().locales(&[&En, &JaJp, &ZhCn, &KoKr]);
+ let parse = |source: &str| LonLat::parse_with(source, &options).unwrap();
+ let expected = parse("43°3′43.5″N 141°21′15.8″E");
+
+ // The locales at the same time
+ assert_eq!(parse("北緯43度3分43.5秒 東経141度21分15.8秒"), expected);
+ assert_eq!(parse("北纬43度3分43.5秒, 东经141度21分15.8秒"), expected);
+ assert_eq!(parse("북위43도3분43.5초 동경141도21분15.8초"), expected);
+ assert_eq!(parse("43 deg 3 min 43.5 sec North, 141°21′15.8″ east"), expected);
+ assert_eq!(parse("北緯四十三度三分四十三・五秒 141°21′15.8″E"), expected);
+
+ // The decimal comma; ";" separates the axes
+ let parse = |source: &str, locales: &'static [&'static dyn Locale]| {
+  LonLat::parse_with(source, &ParseOptions::new().locales(locales)).unwrap()
+ };
+ assert_eq!(parse("43°3′43,5″N; 141°21′15,8″E", &[&Fr]), expected);
+ assert_eq!(parse("43°3′43,5″ nord 141°21′15,8″ est", &[&Fr]), expected);
+ assert_eq!(parse("43°3′43,5″N 141°21′15,8″O", &[&De]), expected);
+ assert_eq!(parse("43,062083 grad nord; 141,354389 grad ost", &[&De]), sapporo());
+ assert_eq!(parse("43°3′43,5″ norte 141°21′15,8″ oeste", &[&Es]).lon.as_degrees(), -expected.lon.as_degrees());
+ assert_eq!(
+  LonLatAlt::parse_with("43,062083; 141,354389; 12,5", &ParseOptions::new().locales(&[&Fr])).unwrap().alt,
+  Length::from_meters(12.5)
+ );
+
+ // The decimal comma in the long numbers as well as the point
+ let digits = "43,".to_string() + &"0".repeat(80) + "5";
+ let fr = ParseOptions::new().locales(&[&Fr]);
+ assert_eq!(Angle::from_dms_str_with(&digits, &fr).unwrap(), Angle::from_dms_str(&digits.replace(',', ".")).unwrap());
+
+ // The earlier locales take precedence for the conflicting letters; "O" is the west in French and the east in German
+ let lon = |locales: &'static [&'static dyn Locale]| Angle::from_dms_str_with("141°O", &ParseOptions::new().locales(locales)).unwrap();
+ assert_eq!(lon(&[&Fr, &De]), Angle::from_degrees(-141.0));
+ assert_eq!(lon(&[&De, &Fr]), Angle::from_degrees(141.0));
+ assert_eq!(lon(&[&Es, &De]), Angle::from_degrees(-141.0));
+
+ // No seconds after the decimal minutes in the strict mode with the decimal comma as well as the point
+ let fr = ParseOptions::strict().locales(&[&Fr]);
+ assert_abs_diff_eq!(Angle::from_dms_str_with("43°3,5′", &fr).unwrap().as_degrees(), 43.0 + 3.5 / 60.0, epsilon = 1.0e-9);
+ match Angle::from_dms_str_with("43°3,5′ 10″", &fr).unwrap_err()
+ {
+  CivilEngineeringLocationError::ParseError(e) => assert_eq!(e.component, ParseComponent::Seconds),
+  other => panic!("{:?}", other)
+ }
+}
+
+#[test]
+fn locales_round_trip()
+{
+ for locale in LOCALES.iter()
+ {
+  let formatted = CoordinateFormatter::new().locale(*locale).separator(" ").format(&sapporo()).unwrap();
+  let parsed = LonLat::parse_with(&formatted, &ParseOptions::new().locales(std::slice::from_ref(locale))).unwrap();
+  assert_eq!(parsed, LonLat::parse_with("43°3′43.5″N 141°21′15.8″E", &ParseOptions::new()).unwrap(), "{}", formatted);
+ }
+}
+
+#[test]
+fn locale_from_tag()
+{
+ assert_eq!(lonlat::locale::locale_from_tag("ja_jp").map(|locale| locale.tag()), Some("ja-JP"));
+ assert_eq!(lonlat::locale::locale_from_tag("ko-KR").map(|locale| locale.tag()), Some("ko-KR"));
+ assert!(lonlat::locale::locale_from_tag("xx").is_none());
+}
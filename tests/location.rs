@@ -0,0 +1,24 @@
+use lonlat::prelude::*;
+
+#[test]
+fn location_from_str_and_display()
+{
+ match "43.062083, 141.354389".parse::<Location>().unwrap()
+ {
+  Location::LonLat(lonlat) =>
+  {
+   assert_eq!(lonlat.lat, Angle::from_degrees(43.062083));
+   assert_eq!(lonlat.lon, Angle::from_degrees(141.354389));
+   assert_eq!(format!("{:#}", Location::LonLat(lonlat)), "43°3’43.5”N 141°21’15.8”E");
+  },
+  other => panic!("{:?}", other)
+ }
+
+ match " Sapporo Station ".parse::<Location>().unwrap()
+ {
+  Location::Keyword(keyword) => assert_eq!(Location::Keyword(keyword).to_string(), "Sapporo Station"),
+  other => panic!("{:?}", other)
+ }
+
+ assert!("  ".parse::<Location>().is_err());
+}
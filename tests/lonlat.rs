@@ -46,6 +46,16 @@ fn lonlat_to()
  assert_eq!(actual.to_string_dms_nwse_space().unwrap(), "42°49’36.0”N 140°48’41.0”E");
  assert_eq!(actual.to_string_dms_nwse_comma().unwrap(), "42°49’36.0”N,140°48’41.0”E");
  assert_eq!(actual.to_string_dms_nwse(", ").unwrap(), "42°49’36.0”N, 140°48’41.0”E");
+ assert_eq!(actual.to_string_ddm_space().unwrap(), "+42°49.600’ +140°48.683’");
+ assert_eq!(actual.to_string_ddm_nwse_comma().unwrap(), "42°49.600’N,140°48.683’E");
+}
+
+#[test]
+fn lonlat_from_ddm()
+{
+ let actual = LonLat::try_from("43°03.725′N 141°21.263′E").unwrap();
+ assert_abs_diff_eq!(actual.get_lat().as_degrees(), 43.0 + 3.725 / 60.0, epsilon = 1.0e-12);
+ assert_abs_diff_eq!(actual.get_lon().as_degrees(), 141.0 + 21.263 / 60.0, epsilon = 1.0e-12);
 }
 #[test]
 fn lonlatalt_to()
@@ -73,6 +83,7 @@ fn lonlat_from_ja_jp()
 
 #[cfg(feature = "ja-JP")]
 #[test]
+#[allow(deprecated)]
 fn lonlat_to_ja_jp()
 {
  let actual_lon = Angle::from_degrees(140.811389);
@@ -91,4 +102,53 @@ fn lonlat_to_ja_jp()
   actual.to_string_dms_nwse_ja_jp(", ").unwrap(),
   "北緯42度49分36.0秒, 東経140度48分41.0秒"
  );
+ let ddm = CoordinateFormatter::new().style(CoordinateStyle::DegreesDecimalMinutes).decimals(ANGLE_DDM_DECIMALS).locale(&JaJp);
+ assert_eq!(ddm.format(&actual).unwrap(), "北緯42度49.600分 東経140度48.683分");
+ assert_eq!(ddm.hemisphere(HemisphereNotation::SignAlways).separator(",").format(&actual).unwrap(), "+42度49.600分,+140度48.683分");
+}
+
+#[test]
+fn lonlat_display()
+{
+ let actual = LonLat::new(Angle::from_degrees(141.354389), Angle::from_degrees(-43.062083));
+ assert_eq!(format!("{}", actual), "-43.062083, 141.354389");
+ assert_eq!(format!("{:.2}", actual), "-43.06, 141.35");
+ assert_eq!(format!("{:#}", actual), "43°3’43.5”S 141°21’15.8”E");
+ assert_eq!(format!("{:#.2}", actual), "43°3’43.50”S 141°21’15.80”E");
+ assert_eq!(format!("[{:>16.1}]", actual), "[    -43.1, 141.4]");
+ // The precision is up to ANGLE_MAXIMUM_DECIMALS
+ assert_eq!(format!("{:.20}", actual), format!("{:.12}", actual));
+ assert_eq!(format!("{:#.14}", actual), format!("{:#.12}", actual));
+
+ let actual = LonLatAlt::new(Angle::from_degrees(141.354389), Angle::from_degrees(43.062083), Length::from_meters(123.4));
+ assert_eq!(actual.to_string(), "43.062083, 141.354389, 123.4");
+ assert_eq!(format!("{:#}", actual), "43°3’43.5”N 141°21’15.8”E 123.4");
+}
+
+#[test]
+fn lonlat_from_str()
+{
+ let expected = LonLat::new(Angle::from_degrees(141.354389), Angle::from_degrees(-43.062083));
+ let actual: LonLat = "-43.062083, 141.354389".parse().unwrap();
+ assert_abs_diff_eq!(actual.lon.as_degrees(), expected.lon.as_degrees(), epsilon = 1.0e-12);
+ assert_abs_diff_eq!(actual.lat.as_degrees(), expected.lat.as_degrees(), epsilon = 1.0e-12);
+
+ // Round trips
+ for text in &[expected.to_string(), format!("{:#.4}", expected)]
+ {
+  let actual: LonLat = text.parse().unwrap();
+  assert_abs_diff_eq!(actual.lon.as_degrees(), expected.lon.as_degrees(), epsilon = 1.0e-8);
+  assert_abs_diff_eq!(actual.lat.as_degrees(), expected.lat.as_degrees(), epsilon = 1.0e-8);
+ }
+
+ let expected = LonLatAlt::new(Angle::from_degrees(141.354389), Angle::from_degrees(43.062083), Length::from_meters(123.4));
+ for text in &[expected.to_string(), format!("{:#.4}", expected)]
+ {
+  let actual: LonLatAlt = text.parse().unwrap();
+  assert_abs_diff_eq!(actual.lon.as_degrees(), expected.lon.as_degrees(), epsilon = 1.0e-8);
+  assert_abs_diff_eq!(actual.lat.as_degrees(), expected.lat.as_degrees(), epsilon = 1.0e-8);
+  assert_abs_diff_eq!(actual.alt.as_meters(), expected.alt.as_meters(), epsilon = 1.0e-9);
+ }
+
+ assert!("not a location".parse::<LonLat>().is_err());
 }
@@ -0,0 +1,65 @@
+use lonlat::prelude::*;
+
+mod common;
+use common::latlon;
+
+fn parse(url: &str) -> (MapService, LonLat, Option<f64>)
+{
+ let link = url.parse::<MapLink>().unwrap();
+ (link.service, link.lonlat, link.zoom)
+}
+
+#[test]
+fn parse_map_links()
+{
+ use MapService::*;
+ assert_eq!(parse("https://www.openstreetmap.org/#map=15/43.0621/141.3544"), (OpenStreetMap, latlon(43.0621, 141.3544), Some(15.0)));
+ assert_eq!(
+  parse("https://www.openstreetmap.org/?mlat=43.06&mlon=141.35#map=12/43.1/141.4"),
+  (OpenStreetMap, latlon(43.06, 141.35), Some(12.0))
+ );
+ assert_eq!(parse("https://www.google.com/maps/@43.0621,141.3544,15z"), (GoogleMaps, latlon(43.0621, 141.3544), Some(15.0)));
+ assert_eq!(
+  parse("https://www.google.co.jp/maps/place/Sapporo/@43.06,141.35,17.5z/data=!3m1!4b1"),
+  (GoogleMaps, latlon(43.06, 141.35), Some(17.5))
+ );
+ assert_eq!(parse("https://maps.google.com/?q=43.06%2C141.35"), (GoogleMaps, latlon(43.06, 141.35), None));
+ assert_eq!(parse("https://www.google.com/maps/search/?api=1&query=-33.86,151.21"), (GoogleMaps, latlon(-33.86, 151.21), None));
+ assert_eq!(parse("https://maps.apple.com/?ll=43.06,141.35&z=15"), (AppleMaps, latlon(43.06, 141.35), Some(15.0)));
+ assert_eq!(parse("https://www.bing.com/maps?cp=43.06~141.35&lvl=15"), (BingMaps, latlon(43.06, 141.35), Some(15.0)));
+ assert_eq!(parse("https://www.bing.com/maps?sp=point.43.06_141.35_Sapporo"), (BingMaps, latlon(43.06, 141.35), None));
+ assert_eq!(parse(" https://maps.gsi.go.jp/#16/43.062083/141.354389/&base=std "), (GsiMaps, latlon(43.062083, 141.354389), Some(16.0)));
+
+ assert!("https://example.com/#15/43.06/141.35".parse::<MapLink>().is_err());
+ assert!("https://www.google.com/maps/@95.0,141.35,15z".parse::<MapLink>().is_err());
+ assert!("https://www.openstreetmap.org/".parse::<MapLink>().is_err());
+}
+
+#[test]
+fn to_string_map_url()
+{
+ let sapporo = latlon(43.062083, 141.354389);
+ assert_eq!(
+  sapporo.to_string_map_url(MapService::OpenStreetMap, Some(15.0)).unwrap(),
+  "https://www.openstreetmap.org/?mlat=43.062083&mlon=141.354389#map=15/43.062083/141.354389"
+ );
+ assert_eq!(
+  sapporo.to_string_map_url(MapService::GoogleMaps, Some(15.0)).unwrap(),
+  "https://www.google.com/maps/@43.062083,141.354389,15z"
+ );
+ assert_eq!(sapporo.to_string_map_url(MapService::GoogleMaps, None).unwrap(), "https://www.google.com/maps?q=43.062083,141.354389");
+ assert_eq!(sapporo.to_string_map_url(MapService::AppleMaps, Some(15.0)).unwrap(), "https://maps.apple.com/?ll=43.062083,141.354389&z=15");
+ assert_eq!(sapporo.to_string_map_url(MapService::BingMaps, None).unwrap(), "https://www.bing.com/maps?cp=43.062083~141.354389");
+ assert_eq!(sapporo.to_string_map_url(MapService::GsiMaps, None).unwrap(), "https://maps.gsi.go.jp/#16/43.062083/141.354389/");
+
+ // [-180..180) as `ToStringGeoUri`
+ assert_eq!(latlon(0.0, 180.0).to_string_map_url(MapService::GoogleMaps, Some(15.0)).unwrap(), "https://www.google.com/maps/@0,-180,15z");
+
+ let south_west = latlon(-33.8688, -70.5);
+ for service in [MapService::OpenStreetMap, MapService::GoogleMaps, MapService::AppleMaps, MapService::BingMaps, MapService::GsiMaps]
+ {
+  let url = south_west.to_string_map_url(service, Some(12.0)).unwrap();
+  let link = url.parse::<MapLink>().unwrap();
+  assert_eq!((link.service, link.lonlat, link.zoom), (service, south_west, Some(12.0)), "{}", url);
+ }
+}
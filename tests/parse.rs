@@ -0,0 +1,186 @@
+use lonlat::prelude::*;
+use std::convert::TryFrom;
+
+fn parse_error<T: std::fmt::Debug>(result: Result<T, CivilEngineeringLocationError>) -> ParseError
+{
+ match result
+ {
+  Err(CivilEngineeringLocationError::ParseError(e)) => e,
+  other => panic!("{:?}", other)
+ }
+}
+
+#[test]
+fn parse_error_angle()
+{
+ let e = parse_error(Angle::from_dms_str("43°30"));
+ assert_eq!(e.component, ParseComponent::Minutes);
+ assert_eq!(e.span, 4..6);
+ assert_eq!(e.found, "30");
+ assert_eq!(e.expected, "′ after the minutes");
+
+ let e = parse_error(Angle::from_dms_str_with("43°3.5′15″", &ParseOptions::strict()));
+ assert_eq!(e.component, ParseComponent::Seconds);
+ assert_eq!(&"43°3.5′15″"[e.span], "15″");
+
+ let e = parse_error(Angle::from_dms_str("43′30°"));
+ assert_eq!(e.component, ParseComponent::Degrees);
+ assert_eq!(e.found, "°");
+
+ let e = parse_error(Angle::from_dms_str("+N"));
+ assert_eq!(e.component, ParseComponent::Sign);
+ assert_eq!(e.span, 0..1);
+
+ let e = parse_error(Angle::from_dms_str("43°3′43.5″N 7"));
+ assert_eq!(e.component, ParseComponent::End);
+ assert_eq!(e.found, "7");
+
+ assert_eq!(
+  e.to_string(),
+  "Could not parse the end at 17..18; found \"7\", expected the end of the angle."
+ );
+}
+
+#[test]
+fn parse_error_lonlat()
+{
+ let e = parse_error(LonLat::try_from(""));
+ assert_eq!(e.component, ParseComponent::Degrees);
+ assert_eq!(e.span, 0..0);
+
+ let e = parse_error(LonLat::try_from("43N"));
+ assert_eq!(e.component, ParseComponent::Separator);
+ assert_eq!(e.span, 3..3);
+
+ let e = parse_error(LonLat::try_from("43N, 44N"));
+ assert_eq!(e.component, ParseComponent::Hemisphere);
+ assert_eq!(e.span, 5..8);
+
+ // The span is in the original source; after the spaces and "geo:"
+ let e = parse_error(LonLat::try_from(" geo:43.0,141°30"));
+ assert_eq!(e.component, ParseComponent::Minutes);
+ assert_eq!(e.span, 15..17);
+
+ // Nothing but the lenient head; the span is empty at the end
+ let e = parse_error(LonLat::try_from("43.0, x"));
+ assert_eq!(e.component, ParseComponent::Degrees);
+ assert_eq!(e.span, 7..7);
+
+ let e = parse_error(LonLatAlt::try_from("43.0, 141.0, abc"));
+ assert_eq!(e.component, ParseComponent::Altitude);
+ assert_eq!(e.span, 13..16);
+
+ let e = parse_error(LonLatAlt::try_from("43.0 141.0"));
+ assert_eq!(e.component, ParseComponent::Separator);
+}
+
+#[test]
+fn parse_lonlat_hemispheres_and_spaces()
+{
+ let lonlat = LonLat::try_from("141E, 43N").unwrap();
+ assert_eq!(lonlat.lon, Angle::from_degrees(141.0));
+ assert_eq!(lonlat.lat, Angle::from_degrees(43.0));
+
+ let expected = LonLat::try_from("43°3′43.5″N,141°21′15.8″E").unwrap();
+ assert_eq!(LonLat::try_from("43° 3′ 43.5″ N 141° 21′ 15.8″ E").unwrap(), expected);
+ assert_eq!(LonLat::try_from("141°21′15.8″E 43°3′43.5″N").unwrap(), expected);
+
+ assert_eq!(LonLat::try_from("N 43°3′43.5″ E 141°21′15.8″").unwrap(), expected);
+ assert_eq!(LonLat::try_from("east 141 deg 21 min 15.8 sec, north 43 deg 3 min 43.5 sec").unwrap(), expected);
+ // A hemisphere just after a unit word or letter ends the unit
+ assert_eq!(LonLat::try_from("43deg3min43.5secN 141deg21min15.8secE").unwrap(), expected);
+ assert_eq!(LonLat::try_from("43d3m43.5sN, 141d21m15.8sE").unwrap(), expected);
+ assert_eq!(Angle::from_dms_str("43deg3min").unwrap(), Angle::from_dms_str("43°3′").unwrap());
+ assert_eq!(LonLat::parse_with("N43°3′43.5″ E141°21′15.8″", &ParseOptions::strict()).unwrap(), expected);
+
+ let lonlatalt = LonLatAlt::try_from("43° 3′ 43.5″ N 141° 21′ 15.8″ E 123.45").unwrap();
+ assert_eq!(lonlatalt.alt, Length::from_meters(123.45));
+}
+
+#[test]
+fn parse_strict_and_lenient()
+{
+ let strict = ParseOptions::strict();
+
+ // Lenient; the default
+ assert_eq!(Angle::from_dms_str("43°75′").unwrap(), Angle::from_degrees(44.25));
+ assert_eq!(Angle::from_dms_str_with("200N", &ParseOptions::lenient()).unwrap(), Angle::from_degrees(200.0));
+ assert!(LonLat::try_from("lat: 43.0, lon: 141.0 (approx)").is_ok());
+ // The comma before the first angle is not the separator
+ assert_eq!(LonLat::parse_with("lat, lon: 43.0, 141.0", &ParseOptions::lenient()).unwrap(), LonLat::try_from("43.0, 141.0").unwrap());
+ // The seconds after the decimal minutes are added as the baseline did
+ let lonlat = LonLat::try_from("43°3.5′20″N, 141E").unwrap();
+ assert_eq!(lonlat.lat, Angle::from_degrees(43.0 + 3.5 / 60.0 + 20.0 / 3600.0));
+
+ let e = parse_error(Angle::from_dms_str_with("43°75′", &strict));
+ assert_eq!(e.component, ParseComponent::Minutes);
+ assert_eq!(e.span, 4..6);
+
+ let e = parse_error(Angle::from_dms_str_with("43°30′75″", &strict));
+ assert_eq!(e.component, ParseComponent::Seconds);
+
+ let e = parse_error(LonLat::parse_with("43°3.5′20″N, 141E", &strict));
+ assert_eq!(e.expected, "no seconds after the decimal minutes");
+
+ let e = parse_error(Angle::from_dms_str_with("200N", &strict));
+ assert_eq!(e.component, ParseComponent::Degrees);
+ assert_eq!(e.expected, "a latitude within ±90°");
+
+ let e = parse_error(LonLat::parse_with("43.0, 200.0", &strict));
+ assert_eq!(e.component, ParseComponent::Degrees);
+ assert_eq!(e.span, 6..11);
+
+ let e = parse_error(LonLat::parse_with("lat: 43.0, 141.0", &strict));
+ assert_eq!(e.component, ParseComponent::Degrees);
+ assert_eq!(e.span, 0..1);
+
+ let e = parse_error(LonLat::parse_with("43.0, 141.0 (approx)", &strict));
+ assert_eq!(e.component, ParseComponent::End);
+ assert_eq!(e.found, "(approx)");
+
+ let expected = LonLat::try_from("43°3′43.5″N,141°21′15.8″E").unwrap();
+ assert_eq!(LonLat::parse_with(" 43°3′43.5″N, 141°21′15.8″E ", &strict).unwrap(), expected);
+ assert_eq!(LonLat::parse_with("141°21′15.8″E 43°3′43.5″N", &strict).unwrap(), expected);
+ assert!(LonLat::parse_with("geo:-90,180", &strict).is_ok());
+ assert_eq!(LonLatAlt::parse_with("43.0, 141.0, 12.5", &strict).unwrap().alt, Length::from_meters(12.5));
+}
+
+#[test]
+fn parse_axis_order()
+{
+ let expected = LonLat::try_from("43.06, 141.35").unwrap();
+ let lon_lat = ParseOptions::default().order(ParseAxisOrder::LonLat);
+ let auto = ParseOptions::default().order(ParseAxisOrder::Auto);
+
+ assert_eq!(LonLat::parse_with("141.35, 43.06", &lon_lat).unwrap(), expected);
+ assert_eq!(LonLat::parse_with("43.06N, 141.35E", &lon_lat).unwrap(), expected);
+ assert_eq!(LonLat::parse_with("141.35, 43.06", &auto).unwrap(), expected);
+ assert_eq!(LonLat::parse_with("43.06, 141.35", &auto).unwrap(), expected);
+
+ let e = parse_error(LonLat::parse_with("43.06, 41.35", &auto));
+ assert_eq!(e.component, ParseComponent::AxisOrder);
+ assert_eq!(e.span, 0..12);
+
+ let japan = LonLatBounds::new(
+  Angle::from_degrees(122.0),
+  Angle::from_degrees(20.0),
+  Angle::from_degrees(154.0),
+  Angle::from_degrees(46.0)
+ );
+ let abkhazia = LonLatBounds::new(
+  Angle::from_degrees(40.0),
+  Angle::from_degrees(42.4),
+  Angle::from_degrees(42.2),
+  Angle::from_degrees(43.6)
+ );
+ let expected = LonLat::try_from("43.06, 41.35").unwrap();
+ assert_eq!(LonLat::parse_with("43.06, 41.35", &auto.clone().reference(abkhazia)).unwrap(), expected);
+ assert_eq!(LonLat::parse_with("41.35, 43.06", &auto.clone().reference(abkhazia)).unwrap(), expected);
+ assert!(LonLat::parse_with("43.06, 41.35", &auto.reference(japan)).is_err());
+
+ // Swapped in the explicit order
+ let e = parse_error(LonLat::parse_with("141.35, 43.06", &ParseOptions::strict()));
+ assert_eq!(e.component, ParseComponent::Degrees);
+ assert_eq!(e.span, 0..6);
+ assert!(e.expected.contains("swapped"));
+}
@@ -0,0 +1,52 @@
+use lonlat::prelude::*;
+use std::{
+ alloc::{
+  GlobalAlloc,
+  Layout,
+  System
+ },
+ cell::Cell,
+ convert::TryFrom
+};
+
+/// Count the allocations of the current thread
+struct CountingAllocator;
+
+thread_local! {
+ static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator
+{
+ unsafe fn alloc(&self, layout: Layout) -> *mut u8
+ {
+  ALLOCATIONS.with(|count| count.set(count.get() + 1));
+  System.alloc(layout)
+ }
+
+ unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout)
+ {
+  System.dealloc(ptr, layout)
+ }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> usize
+{
+ let before = ALLOCATIONS.with(Cell::get);
+ std::hint::black_box(f());
+ ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn parse_does_not_allocate()
+{
+ assert_eq!(allocations(|| Angle::from_dms_str("43°3′43.5″N").unwrap()), 0);
+ assert_eq!(allocations(|| Angle::from_dms_str("-43°3.725′").unwrap()), 0);
+ assert_eq!(allocations(|| LonLat::try_from("43°3′43.5″N 141°21′15.8″E").unwrap()), 0);
+ assert_eq!(allocations(|| LonLat::try_from("geo:43.062083,141.354389").unwrap()), 0);
+ assert_eq!(allocations(|| LonLatAlt::try_from("43.062083, 141.354389, 123.45").unwrap()), 0);
+ assert_eq!(allocations(|| "43.062083 141.354389".parse::<LonLat>().unwrap()), 0);
+}
@@ -0,0 +1,80 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+// 1° of the equator on WGS84
+const EQUATOR_DEGREE: f64 = 111_319.490_793;
+
+fn path() -> LonLatPath
+{
+ LonLatPath::new(vec![lonlat(0.0, 0.0), lonlat(1.0, 0.0), lonlat(2.0, 0.0)])
+}
+
+#[test]
+fn length()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ assert_abs_diff_eq!(path().length(&ellipsoid).unwrap().as_meters(), EQUATOR_DEGREE * 2.0, epsilon = 1.0e-3);
+
+ let chainages = path().vertex_chainages(&ellipsoid).unwrap();
+ assert_eq!(chainages.len(), 3);
+ assert_abs_diff_eq!(chainages[1].as_meters(), EQUATOR_DEGREE, epsilon = 1.0e-3);
+
+ assert_abs_diff_eq!(LonLatPath::new(vec![]).length(&ellipsoid).unwrap().as_meters(), 0.0);
+}
+
+#[test]
+fn point_at_distance()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let actual = path().point_at_distance(Length::from_meters(EQUATOR_DEGREE * 1.5), &ellipsoid).unwrap();
+ assert_abs_diff_eq!(actual.lon.as_degrees(), 1.5, epsilon = 1.0e-9);
+ assert_abs_diff_eq!(actual.lat.as_degrees(), 0.0, epsilon = 1.0e-9);
+
+ let actual = path().point_at_distance(Length::from_kilometers(1000.0), &ellipsoid).unwrap();
+ assert_eq!(actual, lonlat(2.0, 0.0));
+
+ assert!(LonLatPath::new(vec![]).point_at_distance(Length::from_meters(1.0), &ellipsoid).is_err());
+}
+
+#[test]
+fn locate()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+
+ // 0.01° north of the path; on the left side
+ let p = path().locate(&lonlat(1.5, 0.01), &ellipsoid).unwrap();
+ assert_eq!(p.segment_index, 1);
+ assert_abs_diff_eq!(p.point.lon.as_degrees(), 1.5, epsilon = 1.0e-6);
+ assert_abs_diff_eq!(p.point.lat.as_degrees(), 0.0, epsilon = 1.0e-6);
+ assert_abs_diff_eq!(p.chainage().as_meters(), EQUATOR_DEGREE * 1.5, epsilon = 1.0e-2);
+ // 0.01° of the meridian at the equator ≈ 1105.74 [m]
+ assert_abs_diff_eq!(p.cross_track_distance.as_meters(), -1105.74, epsilon = 1.0e-1);
+
+ let xtd = path().cross_track_distance(&lonlat(0.5, -0.01), &ellipsoid).unwrap();
+ assert_abs_diff_eq!(xtd.as_meters(), 1105.74, epsilon = 1.0e-1);
+
+ // Beyond the end
+ let p = path().locate(&lonlat(3.0, 0.0), &ellipsoid).unwrap();
+ assert_eq!(p.point, lonlat(2.0, 0.0));
+ assert_abs_diff_eq!(p.offset().as_meters(), EQUATOR_DEGREE, epsilon = 1.0e-3);
+}
+
+#[test]
+fn locate_diagonal()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let path = LonLatPath::new(vec![lonlat(139.0, 35.0), lonlat(140.0, 36.0)]);
+ let target = lonlat(139.6, 35.3);
+ let p = path.locate(&target, &ellipsoid).unwrap();
+
+ // The nearest point is perpendicular to the segment.
+ let segment = p.point.geodesic_inverse(&lonlat(140.0, 36.0), &ellipsoid).unwrap();
+ let to_target = p.point.geodesic_inverse(&target, &ellipsoid).unwrap();
+ let angle = (to_target.initial_azimuth - segment.initial_azimuth).as_normalize_negative_pi_positive_pi();
+ assert_abs_diff_eq!(angle.as_degrees(), 90.0, epsilon = 1.0e-6);
+ assert_abs_diff_eq!(p.offset().as_meters(), to_target.distance.as_meters(), epsilon = 1.0e-6);
+}
@@ -0,0 +1,92 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+fn polygon(vertices: &[(f64, f64)]) -> LonLatPolygon
+{
+ LonLatPolygon::new(vertices.iter().map(|&(lon, lat)| lonlat(lon, lat)).collect())
+}
+
+#[test]
+fn area_octant()
+{
+ let radius = 6_371_000.0;
+ let sphere = Ellipsoid::sphere(Length::from_meters(radius));
+ let octant = polygon(&[(0.0, 0.0), (90.0, 0.0), (0.0, 90.0)]);
+ assert_abs_diff_eq!(octant.signed_area(&sphere).as_square_meters(), PI / 2.0 * radius * radius, epsilon = 1.0);
+ assert_eq!(octant.winding_order(), WindingOrder::CounterClockwise);
+
+ let reversed = polygon(&[(0.0, 0.0), (0.0, 90.0), (90.0, 0.0)]);
+ assert_abs_diff_eq!(reversed.signed_area(&sphere).as_square_meters(), -PI / 2.0 * radius * radius, epsilon = 1.0);
+ assert_eq!(reversed.winding_order(), WindingOrder::Clockwise);
+ assert_abs_diff_eq!(reversed.area(&sphere).as_square_meters(), PI / 2.0 * radius * radius, epsilon = 1.0);
+}
+
+#[test]
+fn area_polar_cap()
+{
+ // A ring along the 80°N parallel; the area of the zone on the ellipsoid is 2πb²·(q(90°)-q(80°))/2 ≈ 3.9e+12 [m²]
+ let ring: Vec<LonLat> = (0..3600).map(|i| lonlat(i as f64 / 10.0 - 180.0, 80.0)).collect();
+ let actual = LonLatPolygon::new(ring).area(&Ellipsoid::WGS84).as_square_meters();
+
+ let e = Ellipsoid::WGS84.e2().sqrt();
+ let q = |lat: f64| {
+  let s = lat.to_radians().sin();
+  (1.0 - e * e) * (s / (1.0 - e * e * s * s) - 1.0 / (2.0 * e) * ((1.0 - e * s) / (1.0 + e * s)).ln())
+ };
+ let a = Ellipsoid::WGS84.a;
+ let expected = PI * a * a * (q(90.0) - q(80.0));
+ assert_abs_diff_eq!(actual / expected, 1.0, epsilon = 1.0e-5);
+}
+
+#[test]
+fn area_antimeridian()
+{
+ let a = polygon(&[(179.0, 0.0), (-179.0, 0.0), (-179.0, 1.0), (179.0, 1.0)]);
+ let b = polygon(&[(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)]);
+ assert_abs_diff_eq!(
+  a.area(&Ellipsoid::WGS84).as_square_meters(),
+  b.area(&Ellipsoid::WGS84).as_square_meters(),
+  epsilon = 1.0
+ );
+ // ≈ 2 × 12,308 [km²]
+ assert_abs_diff_eq!(a.area(&Ellipsoid::WGS84).as_square_kilometers(), 24_617.0, epsilon = 5.0);
+}
+
+#[test]
+fn perimeter()
+{
+ let p = polygon(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)]);
+ assert_eq!(p.vertices.len(), 4);
+ // 2 × equator(111,319.491 [m]) + 2 × meridian(110,574.389 [m]) + ≈ parallel difference
+ let actual = p.perimeter(&Ellipsoid::WGS84).unwrap().as_meters();
+ assert_abs_diff_eq!(actual, 2.0 * 111_319.491 + 2.0 * 110_574.389 - 17.0, epsilon = 20.0);
+}
+
+#[test]
+fn contains()
+{
+ let p = polygon(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]);
+ assert!(p.contains(&lonlat(5.0, 5.0)));
+ assert!(!p.contains(&lonlat(15.0, 5.0)));
+ assert!(!p.contains(&lonlat(-175.0, -5.0)));
+ assert!(p.contains(&lonlat(0.0, 0.0)));
+ assert!(p.contains(&lonlat(5.0, 0.0)));
+
+ let clockwise = polygon(&[(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)]);
+ assert!(clockwise.contains(&lonlat(5.0, 5.0)));
+ assert!(!clockwise.contains(&lonlat(-175.0, -5.0)));
+
+ let antimeridian = polygon(&[(170.0, -10.0), (-170.0, -10.0), (-170.0, 10.0), (170.0, 10.0)]);
+ assert!(antimeridian.contains(&lonlat(180.0, 0.0)));
+ assert!(!antimeridian.contains(&lonlat(0.0, 0.0)));
+
+ let polar: Vec<LonLat> = (0..36).map(|i| lonlat(i as f64 * 10.0 - 180.0, 80.0)).collect();
+ let polar = LonLatPolygon::new(polar);
+ assert!(polar.contains(&lonlat(0.0, 90.0)));
+ assert!(polar.contains(&lonlat(123.0, 85.0)));
+ assert!(!polar.contains(&lonlat(123.0, 70.0)));
+}
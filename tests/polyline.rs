@@ -0,0 +1,111 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+// The example of the Encoded Polyline Algorithm Format document
+const ENCODED: &str = "_p~iF~ps|U_ulLnnqC_mqNvxq`@";
+
+fn points() -> Vec<LonLat>
+{
+ vec![lonlat(-120.2, 38.5), lonlat(-120.95, 40.7), lonlat(-126.453, 43.252)]
+}
+
+#[test]
+fn encode()
+{
+ assert_eq!(points().to_string_encoded_polyline(PolylinePrecision::E5).unwrap(), ENCODED);
+ assert_eq!(LonLatPath::new(points()).to_string_encoded_polyline(PolylinePrecision::default()).unwrap(), ENCODED);
+ assert_eq!(Vec::<LonLat>::new().to_string_encoded_polyline(PolylinePrecision::E5).unwrap(), "");
+ assert!([lonlat(f64::INFINITY, 0.0)].to_string_encoded_polyline(PolylinePrecision::E5).is_err());
+}
+
+#[test]
+fn decode()
+{
+ let actual = Vec::<LonLat>::from_encoded_polyline_str(ENCODED, PolylinePrecision::E5).unwrap();
+ assert_eq!(actual.len(), 3);
+ for (a, e) in actual.iter().zip(points().iter())
+ {
+  assert_abs_diff_eq!(a.lon.as_degrees(), e.lon.as_degrees(), epsilon = 1.0e-9);
+  assert_abs_diff_eq!(a.lat.as_degrees(), e.lat.as_degrees(), epsilon = 1.0e-9);
+ }
+}
+
+#[test]
+fn round_trip_e6()
+{
+ let source = [lonlat(141.354389, 43.062083), lonlat(140.811389, 42.826667), lonlat(-179.999999, -89.999999)];
+ let encoded = source.to_string_encoded_polyline(PolylinePrecision::E6).unwrap();
+ let actual = LonLatPath::from_encoded_polyline_str(&encoded, PolylinePrecision::E6).unwrap();
+ for (a, e) in actual.vertices.iter().zip(source.iter())
+ {
+  assert_abs_diff_eq!(a.lon.as_degrees(), e.lon.as_degrees(), epsilon = 1.0e-9);
+  assert_abs_diff_eq!(a.lat.as_degrees(), e.lat.as_degrees(), epsilon = 1.0e-9);
+ }
+}
+
+#[test]
+fn decode_malformed()
+{
+ match Vec::<LonLat>::from_encoded_polyline_str("_p~iF ~ps|U", PolylinePrecision::E5)
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylineInvalidCharacter(5, ' ')) => (),
+  other => panic!("{:?}", other)
+ }
+ match Vec::<LonLat>::from_encoded_polyline_str("_p~iF~ps|", PolylinePrecision::E5)
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylineUnexpectedEnd(_)) => (),
+  other => panic!("{:?}", other)
+ }
+ match Vec::<LonLat>::from_encoded_polyline_str("_p~iF", PolylinePrecision::E5)
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylineMissingLongitude(_)) => (),
+  other => panic!("{:?}", other)
+ }
+ match Vec::<LonLat>::from_encoded_polyline_str("~~~~~~~~~~~~~~?", PolylinePrecision::E5)
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylineOverflow(_)) => (),
+  other => panic!("{:?}", other)
+ }
+ // The 13th chunk of a value overflows the 64 bits
+ match Vec::<LonLat>::from_encoded_polyline_str("~~~~~~~~~~~~?", PolylinePrecision::E5)
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylineOverflow(12)) => (),
+  other => panic!("{:?}", other)
+ }
+ // The sum of the deltas overflows
+ match Vec::<LonLat>::from_encoded_polyline_str(&"~~~~~~~~~~~^".repeat(34), PolylinePrecision::E5)
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylineOverflow(384)) => (),
+  other => panic!("{:?}", other)
+ }
+}
+
+#[test]
+fn precision_out_of_range()
+{
+ let points = points();
+ match points.to_string_encoded_polyline(PolylinePrecision::Digits(30))
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylinePrecisionOutOfRange(30)) => (),
+  other => panic!("{:?}", other)
+ }
+ match Vec::<LonLat>::from_encoded_polyline_str("_p~iF~ps|U", PolylinePrecision::Digits(11))
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylinePrecisionOutOfRange(11)) => (),
+  other => panic!("{:?}", other)
+ }
+ let precision = PolylinePrecision::Digits(POLYLINE_MAXIMUM_DIGITS);
+ let encoded = points.to_string_encoded_polyline(precision).unwrap();
+ let decoded = Vec::<LonLat>::from_encoded_polyline_str(&encoded, precision).unwrap();
+ assert_eq!(decoded.len(), points.len());
+
+ match [lonlat(1.0e300, 0.0)].to_string_encoded_polyline(PolylinePrecision::E5)
+ {
+  Err(CivilEngineeringLocationError::EncodedPolylineValueOutOfRange(0)) => (),
+  other => panic!("{:?}", other)
+ }
+}
@@ -0,0 +1,57 @@
+use lonlat::prelude::*;
+
+use approx::assert_abs_diff_eq;
+
+mod common;
+use common::lonlat;
+
+#[test]
+fn rhumb_inverse_sphere()
+{
+ // Dover -> Calais; R = 6371 [km]
+ let sphere = Ellipsoid::sphere(Length::from_kilometers(6371.0));
+ let dover = LonLat::new(Angle::from_dms(1.0, 20.0, 17.0).unwrap(), Angle::from_dms(51.0, 7.0, 32.0).unwrap());
+ let calais = LonLat::new(Angle::from_dms(1.0, 51.0, 9.0).unwrap(), Angle::from_dms(50.0, 57.0, 48.0).unwrap());
+ let actual = dover.rhumb_inverse(&calais, &sphere);
+ assert_abs_diff_eq!(actual.distance.as_kilometers(), 40.2349, epsilon = 1.0e-4);
+ assert_abs_diff_eq!(actual.bearing.as_degrees(), 116.6362, epsilon = 1.0e-4);
+}
+
+#[test]
+fn rhumb_inverse_ellipsoid()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+
+ // Along the equator
+ let actual = lonlat(0.0, 0.0).rhumb_inverse(&lonlat(1.0, 0.0), &ellipsoid);
+ assert_abs_diff_eq!(actual.distance.as_meters(), 111_319.491, epsilon = 1.0e-3);
+ assert_abs_diff_eq!(actual.bearing.as_degrees(), 90.0, epsilon = 1.0e-9);
+
+ // Along a meridian; the quarter meridian of WGS84 is 10,001,965.729 [m]
+ let actual = lonlat(10.0, 0.0).rhumb_inverse(&lonlat(10.0, 90.0), &ellipsoid);
+ assert_abs_diff_eq!(actual.distance.as_meters(), 10_001_965.729, epsilon = 1.0e-2);
+ assert_abs_diff_eq!(actual.bearing.as_degrees(), 0.0, epsilon = 1.0e-9);
+
+ // Across the antimeridian
+ let actual = lonlat(179.0, 10.0).rhumb_inverse(&lonlat(-179.0, 10.0), &ellipsoid);
+ assert_abs_diff_eq!(actual.bearing.as_degrees(), 90.0, epsilon = 1.0e-9);
+ assert!(actual.distance.as_kilometers() < 220.0);
+}
+
+#[test]
+fn rhumb_destination()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+ let (a, b) = (lonlat(139.767, 35.681), lonlat(-122.419, 37.775));
+ let inverse = a.rhumb_inverse(&b, &ellipsoid);
+ assert!(inverse.bearing.as_degrees() > 0.0);
+
+ let actual = a.rhumb_destination(inverse.bearing, inverse.distance, &ellipsoid).unwrap();
+ assert_abs_diff_eq!(actual.lon.as_degrees(), b.lon.as_degrees(), epsilon = 1.0e-9);
+ assert_abs_diff_eq!(actual.lat.as_degrees(), b.lat.as_degrees(), epsilon = 1.0e-9);
+
+ let actual = lonlat(179.5, 0.0).rhumb_destination(Angle::from_degrees(90.0), Length::from_meters(111_319.491), &ellipsoid).unwrap();
+ assert_abs_diff_eq!(actual.lon.as_degrees(), -179.5, epsilon = 1.0e-8);
+
+ assert!(lonlat(0.0, 89.0).rhumb_destination(Angle::from_degrees(10.0), Length::from_kilometers(500.0), &ellipsoid).is_err());
+}
@@ -0,0 +1,65 @@
+use lonlat::prelude::*;
+
+mod common;
+use common::lonlat;
+
+fn lonlatalt(lon: f64, lat: f64, alt: f64) -> LonLatAlt
+{
+ LonLatAlt::new(Angle::from_degrees(lon), Angle::from_degrees(lat), Length::from_meters(alt))
+}
+
+// A straight track along the equator with a 0.001°(≈111 [m]) bump at the middle
+fn track() -> Vec<LonLat>
+{
+ let mut track: Vec<LonLat> = (0..=100).map(|i| lonlat(i as f64 * 0.001, 0.0)).collect();
+ track[50] = lonlat(0.05, 0.001);
+ track
+}
+
+#[test]
+fn douglas_peucker()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+
+ let actual = track().simplify_douglas_peucker(Length::from_meters(10.0), &ellipsoid);
+ assert_eq!(actual, vec![lonlat(0.0, 0.0), lonlat(0.049, 0.0), lonlat(0.05, 0.001), lonlat(0.051, 0.0), lonlat(0.1, 0.0)]);
+
+ let actual = track().simplify_douglas_peucker(Length::from_meters(200.0), &ellipsoid);
+ assert_eq!(actual, vec![lonlat(0.0, 0.0), lonlat(0.1, 0.0)]);
+
+ let single = vec![lonlat(1.0, 2.0)];
+ assert_eq!(single.simplify_douglas_peucker(Length::from_meters(1.0), &ellipsoid), single);
+}
+
+#[test]
+fn visvalingam()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+
+ let actual = track().simplify_visvalingam(Length::from_meters(10.0), &ellipsoid);
+ assert_eq!(actual.first(), Some(&lonlat(0.0, 0.0)));
+ assert_eq!(actual.last(), Some(&lonlat(0.1, 0.0)));
+ assert!(actual.contains(&lonlat(0.05, 0.001)));
+ assert!(actual.len() <= 5);
+
+ let actual = track().simplify_visvalingam(Length::from_kilometers(10.0), &ellipsoid);
+ assert_eq!(actual, vec![lonlat(0.0, 0.0), lonlat(0.1, 0.0)]);
+}
+
+#[test]
+fn with_alt()
+{
+ let ellipsoid = Ellipsoid::WGS84;
+
+ // Flat in 2-D, but a 50 [m] climb at the middle
+ let mut track: Vec<LonLatAlt> = (0..=10).map(|i| lonlatalt(i as f64 * 0.001, 0.0, 0.0)).collect();
+ track[5].alt = Length::from_meters(50.0);
+
+ assert_eq!(track.simplify_douglas_peucker(Length::from_meters(10.0), &ellipsoid).len(), 2);
+ let actual = track.simplify_douglas_peucker_with_alt(Length::from_meters(10.0), &ellipsoid);
+ assert_eq!(actual.len(), 5);
+ assert!(actual.contains(&track[5]));
+
+ let actual = track.simplify_visvalingam_with_alt(Length::from_meters(10.0), &ellipsoid);
+ assert!(actual.contains(&track[5]));
+}